@@ -0,0 +1,32 @@
+use jnat::{Env, JavaVm};
+use std::{path::Path, process::Command};
+
+/// Compiles a class of integration/java and creates a JVM with it on the classpath
+///
+/// The JVM is stored, so it lives until the test exits, and the environment of the current thread
+/// is returned.
+///
+/// # Arguments
+///
+/// * `class` - The name of the class, e.g. `Embedded`
+pub fn java_env(class: &str) -> Env<'static> {
+  let out = Path::new(env!("CARGO_TARGET_TMPDIR")).join(env!("CARGO_CRATE_NAME"));
+
+  // Compile the Java class
+  let status = Command::new("javac")
+    .arg("-d")
+    .arg(&out)
+    .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("integration/java/{}.java", class)))
+    .status()
+    .expect("Failed to spawn javac");
+  assert!(status.success(), "Failed to compile {}", class);
+
+  JavaVm::builder()
+    .classpath(&out)
+    .option("-Xmx64m")
+    .build()
+    .expect("Failed to create JVM")
+    .store();
+
+  Env::current().expect("Failed to get the environment")
+}
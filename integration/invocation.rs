@@ -0,0 +1,41 @@
+use jnat::{jni::objects::JString, Signature, Type, Value};
+
+mod common;
+
+#[test]
+fn invocation() {
+  let env = common::java_env("Embedded");
+  let class = env.get_class("Embedded").expect("Failed to get class");
+
+  let sum = class
+    .call_static_method(
+      "add",
+      Signature::new(&[Type::Int, Type::Int], Type::Int),
+      &[Value::Int(2), Value::Int(3)],
+    )
+    .expect("Failed to call add")
+    .i()
+    .expect("add did not return an int");
+  assert_eq!(sum, 5);
+
+  let name = env.new_string("embedded").expect("Failed to create string");
+  let greeting = class
+    .call_static_method(
+      "greet",
+      Signature::new(
+        &[Type::Object("java/lang/String")],
+        Type::Object("java/lang/String"),
+      ),
+      &[Value::Object(jnat::Object::new(&env, &name))],
+    )
+    .expect("Failed to call greet")
+    .l()
+    .expect("greet did not return an object");
+
+  let mut jni_env = env.get_jni_env();
+  let greeting: String = jni_env
+    .get_string(&JString::from(greeting))
+    .expect("Failed to read string")
+    .into();
+  assert_eq!(greeting, "Hello, embedded!");
+}
@@ -0,0 +1,49 @@
+import java.nio.ByteBuffer;
+
+public class Buffers {
+  private static native ByteBuffer shared(int length);
+
+  private static native int sum(ByteBuffer buffer);
+
+  private static native void fill(ByteBuffer buffer, byte value);
+
+  private static native int released();
+
+  static {
+    System.loadLibrary("buffers");
+  }
+
+  public static void main(String[] args) throws InterruptedException {
+    ByteBuffer shared = Buffers.shared(5);
+    System.out.println(shared.isDirect() + " " + shared.capacity() + " " + shared.get(4));
+
+    ByteBuffer direct = ByteBuffer.allocateDirect(4);
+    direct.put(new byte[] { 1, 2, 3, 4 });
+    System.out.println(Buffers.sum(direct));
+
+    Buffers.fill(direct, (byte) 7);
+    System.out.println(direct.get(0) + " " + direct.get(3));
+
+    try {
+      Buffers.fill(direct.asReadOnlyBuffer(), (byte) 8);
+    } catch (RuntimeException e) {
+      System.out.println(e.getClass().getName() + " " + direct.get(0));
+    }
+
+    try {
+      Buffers.sum(ByteBuffer.allocate(4));
+    } catch (RuntimeException e) {
+      System.out.println(e.getClass().getName());
+    }
+
+    // The memory of the shared buffer is freed once it is garbage collected
+    shared = null;
+    int released = 0;
+    for (int i = 0; i < 100 && released == 0; i++) {
+      System.gc();
+      Thread.sleep(10);
+      released = Buffers.released();
+    }
+    System.out.println(released);
+  }
+}
@@ -0,0 +1,32 @@
+import java.io.EOFException;
+import java.io.FileNotFoundException;
+
+public class Catch {
+  private static native void method();
+
+  static {
+    System.loadLibrary("catch");
+  }
+
+  public static void main(String[] args) {
+    try {
+      Catch.method();
+    } catch (IllegalArgumentException e) {
+      System.out.println("rethrown " + e.getMessage());
+    }
+  }
+
+  public static void missing() throws FileNotFoundException {
+    throw new FileNotFoundException("config.txt");
+  }
+
+  public static void truncated() throws EOFException {
+    throw new EOFException("truncated");
+  }
+
+  public static void invalid() {
+    throw new IllegalArgumentException("invalid");
+  }
+
+  public static void succeed() {}
+}
@@ -0,0 +1,23 @@
+public class Checked {
+  private static native void method(Object string);
+
+  static {
+    System.loadLibrary("checked");
+  }
+
+  private static int length(String s) {
+    return s.length();
+  }
+
+  private static int parse(Integer value) {
+    return value;
+  }
+
+  public static void main(String[] args) {
+    try {
+      Checked.method("text");
+    } catch (IllegalStateException e) {
+      System.out.println(e.getMessage());
+    }
+  }
+}
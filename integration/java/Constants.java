@@ -0,0 +1,24 @@
+public class Constants {
+  private static native void method();
+
+  public static final int VERSION = 3;
+  public static final long MAGIC = 0xCAFEBABEL;
+  public static final String NAME = "jnat";
+  public static final char SEPARATOR = ':';
+  public static final boolean ENABLED = true;
+  public static final double RATIO = 0.5;
+  public static final char HIGH_SURROGATE = '\uD800';
+  public static final Object NOT_PRIMITIVE = new Object();
+  public static int notFinal = 0;
+  public final int notStatic = 0;
+
+  static {
+    System.loadLibrary("constants");
+  }
+
+  public static void main(String[] args) {
+    // Adds the synthetic $assertionsDisabled field
+    assert args != null;
+    Constants.method();
+  }
+}
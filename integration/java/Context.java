@@ -0,0 +1,17 @@
+public class Context {
+  private int count = 1;
+
+  private static native void method(Context context);
+
+  static {
+    System.loadLibrary("context");
+  }
+
+  private static int twice(int value) {
+    return value * 2;
+  }
+
+  public static void main(String[] args) {
+    Context.method(new Context());
+  }
+}
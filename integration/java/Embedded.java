@@ -0,0 +1,19 @@
+public class Embedded {
+  public static final String GREETING = "Hello";
+
+  public static final String FAREWELL = "Goodbye";
+
+  public static int add(int a, int b) {
+    return a + b;
+  }
+
+  public static String greet(String name) {
+    return "Hello, " + name + "!";
+  }
+
+  private static native void local();
+
+  public static void callLocal() {
+    Embedded.local();
+  }
+}
@@ -0,0 +1,22 @@
+public class Enumeration {
+  private static native void method(Color color);
+
+  static {
+    System.loadLibrary("enumeration");
+  }
+
+  public static void main(String[] args) {
+    Enumeration.method(Color.GREEN);
+  }
+
+  public static void print(Color color) {
+    System.out.println(color);
+  }
+}
+
+enum Color {
+  RED,
+  GREEN,
+  DARK_BLUE,
+  PURPLE,
+}
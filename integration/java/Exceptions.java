@@ -0,0 +1,25 @@
+import java.io.IOException;
+
+public class Exceptions {
+  private static native void method();
+
+  static {
+    System.loadLibrary("exceptions");
+  }
+
+  public static void main(String[] args) {
+    try {
+      Exceptions.method();
+    } catch (IllegalStateException e) {
+      System.out.println("rethrown " + e.getMessage());
+    }
+  }
+
+  public static void fail() {
+    throw new IllegalStateException("outer", new IOException("inner"));
+  }
+
+  public static int succeed() {
+    return 1;
+  }
+}
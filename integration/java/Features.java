@@ -0,0 +1,27 @@
+public class Features {
+  private static native boolean atLeast9();
+
+  private static native String module(Class<?> c);
+
+  private static native boolean isVirtual(Thread thread);
+
+  private static native boolean unsupported();
+
+  static {
+    System.loadLibrary("features");
+  }
+
+  public static void main(String[] args) {
+    System.out.println(Features.atLeast9());
+    System.out.println(Features.module(String.class));
+
+    try {
+      System.out.println(Features.isVirtual(Thread.currentThread()));
+    } catch (UnsupportedOperationException e) {
+      // JVMs older than 21 do not have virtual threads
+      System.out.println(Runtime.version().feature() < 21 ? "false" : e.getMessage());
+    }
+
+    System.out.println(Features.unsupported());
+  }
+}
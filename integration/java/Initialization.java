@@ -0,0 +1,18 @@
+public class Initialization {
+  private static native void method(Class<?> lazy);
+
+  static {
+    System.loadLibrary("initialization");
+  }
+
+  public static void main(String[] args) {
+    // Class literals do not initialize the class, unlike FindClass
+    Initialization.method(Lazy.class);
+  }
+}
+
+class Lazy {
+  static {
+    System.out.println("initialized");
+  }
+}
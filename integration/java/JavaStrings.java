@@ -0,0 +1,18 @@
+public class JavaStrings {
+  private static native String describe(String s, String other);
+
+  private static native String intern(String s);
+
+  static {
+    System.loadLibrary("java_string");
+  }
+
+  private static String greet(String name) {
+    return "Hello, " + name + "!";
+  }
+
+  public static void main(String[] args) {
+    System.out.println(JavaStrings.describe("hello", "help"));
+    System.out.println(JavaStrings.intern(new String("hello")) == "hello");
+  }
+}
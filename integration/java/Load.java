@@ -0,0 +1,18 @@
+public class Load {
+  private static String loaded;
+
+  private static native String current();
+
+  static {
+    System.loadLibrary("load");
+  }
+
+  private static void loaded(String message) {
+    loaded = message;
+  }
+
+  public static void main(String[] args) {
+    System.out.println(loaded);
+    System.out.println(Load.current());
+  }
+}
@@ -0,0 +1,33 @@
+import java.util.ArrayList;
+import java.util.List;
+import java.util.logging.Handler;
+import java.util.logging.Level;
+import java.util.logging.LogRecord;
+import java.util.logging.Logger;
+
+public class LogCapture extends Handler {
+  private static final List<String> records = new ArrayList<>();
+
+  public static void install() {
+    Logger root = Logger.getLogger("");
+    root.setLevel(Level.ALL);
+    root.addHandler(new LogCapture());
+  }
+
+  public static synchronized String records() {
+    return String.join("\n", records);
+  }
+
+  @Override
+  public void publish(LogRecord record) {
+    synchronized (LogCapture.class) {
+      records.add(record.getLevel() + " " + record.getLoggerName() + ": " + record.getMessage());
+    }
+  }
+
+  @Override
+  public void flush() {}
+
+  @Override
+  public void close() {}
+}
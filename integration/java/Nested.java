@@ -0,0 +1,61 @@
+public class Nested {
+  private static native void method(Nested outer);
+
+  private String name = "outer";
+  private static String label = "static";
+
+  static {
+    System.loadLibrary("nested");
+  }
+
+  public static void main(String[] args) {
+    Nested outer = new Nested();
+    Nested.method(outer);
+  }
+
+  public class Inner {
+    public Inner(String suffix, int count) {
+      System.out.println(name + " " + suffix + " " + count);
+    }
+  }
+
+  public static class StaticNested {
+    public StaticNested(int count) {
+      System.out.println("static nested " + count);
+    }
+  }
+
+  public static void staticLocal() {
+    class StaticLocal {
+      StaticLocal() {
+        System.out.println("static local");
+      }
+    }
+
+    if (label == null) {
+      new StaticLocal();
+    }
+  }
+
+  public void local() {
+    class Local {
+      Local() {
+        System.out.println(name + " local");
+      }
+    }
+
+    if (name == null) {
+      new Local();
+    }
+  }
+
+  public void anonymous() {
+    if (name == null) {
+      new Object() {
+        {
+          System.out.println(name + " anonymous");
+        }
+      };
+    }
+  }
+}
@@ -0,0 +1,27 @@
+public class Panic {
+  private static native int divide(int a, int b);
+
+  private static native String name(int index);
+
+  static {
+    System.loadLibrary("panic");
+  }
+
+  public static void main(String[] args) {
+    System.out.println(Panic.divide(6, 3));
+
+    try {
+      Panic.divide(1, 0);
+    } catch (RuntimeException e) {
+      System.out.println(e.getClass().getName() + ": " + e.getMessage());
+      StackTraceElement[] stackTrace = e.getStackTrace();
+      System.out.println(stackTrace[0] + " ... " + stackTrace[stackTrace.length - 1]);
+    }
+
+    try {
+      Panic.name(3);
+    } catch (IllegalStateException e) {
+      System.out.println(e.getClass().getName() + ": " + e.getMessage());
+    }
+  }
+}
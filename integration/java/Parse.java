@@ -0,0 +1,25 @@
+public class Parse {
+  private static native int parse(String s);
+
+  private static native int missing();
+
+  static {
+    System.loadLibrary("parse");
+  }
+
+  public static void main(String[] args) {
+    for (String s : new String[] { "42", "abc" }) {
+      try {
+        System.out.println(Parse.parse(s));
+      } catch (NumberFormatException e) {
+        System.out.println(e.getClass().getName() + ": " + e.getMessage());
+      }
+    }
+
+    try {
+      Parse.missing();
+    } catch (RuntimeException e) {
+      System.out.println(e.getClass().getName() + ": " + e.getMessage());
+    }
+  }
+}
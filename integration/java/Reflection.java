@@ -0,0 +1,30 @@
+public class Reflection extends Base {
+  private static native void method(Reflection obj, String string);
+
+  private int secret = 42;
+  private static String label = "static";
+
+  static {
+    System.loadLibrary("reflection");
+  }
+
+  public static void main(String[] args) {
+    Reflection.method(new Reflection(), "string");
+  }
+
+  private String greet(String name, int times) {
+    return name + " x" + times;
+  }
+
+  private static int twice(int n) {
+    return n * 2;
+  }
+}
+
+class Base {
+  private long inherited = 7L;
+
+  private void hidden() {
+    System.out.println("hidden");
+  }
+}
@@ -0,0 +1,19 @@
+public class Strings {
+  private static native String describe(String s);
+
+  private static native String roundTrip(String s);
+
+  static {
+    System.loadLibrary("strings");
+  }
+
+  public static void main(String[] args) {
+    String[] strings = { "", "hello", "h\u00e9llo \uD83D\uDE00", "a\uD83Db", "nul\u0000" };
+
+    for (String s : strings) {
+      System.out.println(Strings.describe(s));
+    }
+
+    System.out.println(Strings.roundTrip("a\uD83Db").equals("a\uD83Db"));
+  }
+}
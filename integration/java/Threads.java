@@ -0,0 +1,26 @@
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.List;
+
+public class Threads {
+  private static final List<String> results = Collections.synchronizedList(new ArrayList<>());
+
+  private static native void run();
+
+  static {
+    System.loadLibrary("threads");
+  }
+
+  private static void record(String result) {
+    results.add(result + " " + Thread.currentThread().isDaemon());
+  }
+
+  public static void main(String[] args) {
+    Threads.run();
+
+    Collections.sort(results);
+    for (String result : results) {
+      System.out.println(result);
+    }
+  }
+}
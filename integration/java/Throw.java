@@ -0,0 +1,28 @@
+public class Throw {
+  private static native void method(int scenario, Throwable existing);
+
+  static {
+    System.loadLibrary("throw");
+  }
+
+  public static void main(String[] args) {
+    for (int scenario = 0; scenario < 5; scenario++) {
+      try {
+        Throw.method(scenario, new UnsupportedOperationException("existing"));
+        System.out.println("nothing thrown");
+      } catch (Exception e) {
+        System.out.print(e.getClass().getName() + ": " + e.getMessage());
+
+        if (e.getCause() != null) {
+          System.out.print(" (caused by " + e.getCause().getMessage() + ")");
+        }
+
+        if (e.getMessage().equals("traced")) {
+          System.out.print(" at " + e.getStackTrace()[0]);
+        }
+
+        System.out.println();
+      }
+    }
+  }
+}
@@ -0,0 +1,15 @@
+public class TooNew {
+  public static void main(String[] args) {
+    boolean loaded;
+
+    try {
+      System.loadLibrary("too_new");
+      loaded = true;
+    } catch (UnsatisfiedLinkError e) {
+      loaded = false;
+    }
+
+    // JNI 24 is supported from Java 24
+    System.out.println("loaded if supported " + (loaded == (Runtime.version().feature() >= 24)));
+  }
+}
@@ -0,0 +1,32 @@
+import java.net.URL;
+import java.net.URLClassLoader;
+import java.nio.file.Paths;
+
+public class Unload {
+  public static void main(String[] args) throws Exception {
+    load();
+
+    // The library is unloaded once its class loader is collected
+    for (int i = 0; i < 200 && System.getProperty("unloaded") == null; i++) {
+      System.gc();
+      Thread.sleep(10);
+    }
+
+    System.out.println("unloaded " + System.getProperty("unloaded"));
+  }
+
+  private static void load() throws Exception {
+    URL[] urls = {Paths.get("out").toUri().toURL()};
+
+    // Without a parent, the loader loads its own UnloadTarget
+    try (URLClassLoader loader = new URLClassLoader(urls, null)) {
+      Class.forName("UnloadTarget", true, loader);
+    }
+  }
+}
+
+class UnloadTarget {
+  static {
+    System.loadLibrary("unload");
+  }
+}
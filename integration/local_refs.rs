@@ -0,0 +1,105 @@
+use jnat::{
+  jnat_macros::{jnat, JavaConstants},
+  jni::{objects::JClass, JNIEnv, NativeMethod},
+  Array, Env, JavaConstants, Object, Signature, Type, Value,
+};
+use std::{
+  ffi::c_void,
+  sync::atomic::{AtomicUsize, Ordering},
+};
+
+mod common;
+
+#[derive(JavaConstants)]
+#[jnat(class = "Embedded")]
+struct Greeting {
+  #[jnat(name = "GREETING")]
+  greeting: String,
+}
+
+/// The references created by `local` that were left when it returned
+static LEFT: AtomicUsize = AtomicUsize::new(0);
+
+jnat!(Embedded, local, (JNIEnv, JClass) -> ());
+
+fn local(env: JNIEnv, _: JClass) {
+  let env = Env::new(&env);
+  let scope = env.local_ref_scope();
+
+  env.get_class("Embedded").expect("Failed to get class");
+  LEFT.store(scope.count(), Ordering::SeqCst);
+
+  // The class is left for the JVM to free when the method returns
+  std::mem::forget(scope);
+}
+
+#[test]
+fn local_refs() {
+  let env = common::java_env("Embedded");
+  let scope = env.local_ref_scope();
+
+  let class = env.get_class("Embedded").expect("Failed to get class");
+  let name = env.new_string("leak").expect("Failed to create string");
+  let line = line!() - 1;
+  let array = env.new_int_array(4);
+  let greeting = class
+    .call_static_method(
+      "greet",
+      Signature::new(
+        &[Type::Object("java/lang/String")],
+        Type::Object("java/lang/String"),
+      ),
+      &[Value::Object(Object::new(&env, &name))],
+    )
+    .expect("Failed to call greet")
+    .l()
+    .expect("greet did not return an object");
+
+  assert_eq!(scope.count(), 4);
+
+  env
+    .delete_local_ref(greeting)
+    .expect("Failed to delete greeting");
+  env
+    .delete_local_ref(array.get_array())
+    .expect("Failed to delete array");
+  env
+    .delete_local_ref(class.get_class())
+    .expect("Failed to delete class");
+
+  let leaked = scope.leaked();
+  assert_eq!(leaked.len(), 1);
+  assert_eq!(leaked[0].function(), "Env::new_string");
+  assert_eq!(leaked[0].location().file(), file!());
+  assert_eq!(leaked[0].location().line(), line);
+
+  env.delete_local_ref(name).expect("Failed to delete name");
+  scope.assert_balanced();
+
+  // The class and the constants that are not returned are deleted, including FAREWELL
+  let constants = Greeting::load(&env).expect("Failed to load constants");
+  assert_eq!(constants.greeting, "Hello");
+  scope.assert_balanced();
+
+  // The references of a native method are forgotten when it returns, as the JVM frees them
+  let mut jni_env = env.get_jni_env();
+  let local = NativeMethod {
+    name: "local".into(),
+    sig: "()V".into(),
+    fn_ptr: Java_Embedded_local as *mut c_void,
+  };
+  jni_env
+    .register_native_methods("Embedded", &[local])
+    .expect("Failed to register local");
+
+  let class = env.get_class("Embedded").expect("Failed to get class");
+  class
+    .call_static_method("callLocal", Signature::new(&[], Type::Void), &[])
+    .expect("Failed to call callLocal");
+  assert_eq!(LEFT.load(Ordering::SeqCst), 1);
+
+  env
+    .delete_local_ref(class.get_class())
+    .expect("Failed to delete class");
+  scope.assert_balanced();
+}
@@ -0,0 +1,75 @@
+use jnat::{jni::objects::JString, JavaLogger, LogBackend, Signature, Type};
+use log::{Level, LevelFilter, Log, Record};
+use std::thread;
+
+mod common;
+
+/// Logs the records of the test to a logger, from this thread and another one
+///
+/// # Arguments
+///
+/// * `log` - Logs a record with a level, target and message
+fn log_records(log: impl Fn(Level, &str, &str) + Sync) {
+  log(Level::Error, module_path!(), "from main");
+  log(Level::Debug, module_path!(), "debug 42");
+  log(Level::Trace, module_path!(), "filtered out");
+
+  thread::scope(|scope| {
+    scope.spawn(|| log(Level::Warn, "other", "from thread"));
+  });
+}
+
+#[test]
+fn logging() {
+  let env = common::java_env("LogCapture");
+  let class = env.get_class("LogCapture").expect("Failed to get class");
+
+  class
+    .call_static_method("install", Signature::new(&[], Type::Void), &[])
+    .expect("Failed to call install");
+
+  // The logger uses the stored JVM
+  JavaLogger::new(LogBackend::JavaUtilLogging)
+    .level(LevelFilter::Debug)
+    .init()
+    .expect("Failed to install logger");
+
+  log_records(|level, target, message| log::log!(target: target, level, "{}", message));
+
+  // Only one logger can be installed, so this one is called directly, with its own module path.
+  // The default LoggerFinder logs System.Logger records to java.util.logging.
+  let logger = JavaLogger::new(LogBackend::SystemLogger).level(LevelFilter::Debug);
+
+  log_records(|level, target, message| {
+    logger.log(
+      &Record::builder()
+        .level(level)
+        .target(target)
+        .module_path(Some("logging::system"))
+        .args(format_args!("{}", message))
+        .build(),
+    )
+  });
+
+  let records = class
+    .call_static_method(
+      "records",
+      Signature::new(&[], Type::Object("java/lang/String")),
+      &[],
+    )
+    .expect("Failed to call records")
+    .l()
+    .expect("records did not return an object");
+
+  let mut jni_env = env.get_jni_env();
+  let records: String = jni_env
+    .get_string(&JString::from(records))
+    .expect("Failed to read string")
+    .into();
+
+  assert_eq!(
+    records,
+    "SEVERE logging: from main\nFINE logging: debug 42\nWARNING logging: from thread\n\
+     SEVERE logging.system: from main\nFINE logging.system: debug 42\nWARNING logging.system: from thread"
+  );
+}
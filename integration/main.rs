@@ -2,6 +2,8 @@ extern crate pretty_env_logger;
 #[macro_use]
 extern crate log;
 
+// The checkers return their result explicitly
+#[allow(clippy::needless_return)]
 pub mod tests;
 
 use std::{
@@ -49,6 +51,8 @@ fn setup() {
 fn teardown() {}
 
 // TODO: Better output instead of just logging
+// The harness predates clippy's lints for these patterns
+#[allow(clippy::expect_fun_call, clippy::redundant_locals)]
 fn main() {
   setup();
 
@@ -170,11 +174,20 @@ fn main() {
     remove_file(Path::new("out").join(get_dylib_name(t.lib)))
       .expect(format!("Failed to remove {}", get_dylib_name(t.lib)).as_str());
 
-    remove_file(Path::new("out").join(format!("{}.h", t.java_class)))
-      .expect(format!("Failed to remove {}.h", t.java_class).as_str());
+    // javac only writes a header for classes declaring native methods
+    let header = Path::new("out").join(format!("{}.h", t.java_class));
+    if header.exists() {
+      remove_file(header).expect(format!("Failed to remove {}.h", t.java_class).as_str());
+    }
+
+    // Java files may declare more than one class, so remove every class file
+    for path in read_dir(Path::new("out")).expect("Failed to read out directory") {
+      let path = path.expect("Failed to get path").path();
 
-    remove_file(Path::new("out").join(format!("{}.class", t.java_class)))
-      .expect(format!("Failed to remove {}.class", t.java_class).as_str());
+      if path.extension().is_some_and(|e| e == "class") {
+        remove_file(&path).unwrap_or_else(|_| panic!("Failed to remove {}", path.display()));
+      }
+    }
 
     #[cfg(target_os = "windows")]
     {
@@ -8,7 +8,10 @@ fn test_array(s: String) -> bool {
 0000000000-0.1000000000
 0000000000-1000000000
 0000000000-1000000000
-0000000000-1000000000\n";
+0000000000-1000000000
+Index 10 out of bounds for length 10
+Index 18446744073709551615 out of bounds for length 10
+Index 0 out of bounds for length 0\n";
 }
 
 inventory::submit! {IntegrationTest {
@@ -0,0 +1,17 @@
+use super::IntegrationTest;
+
+fn test_buffers(s: String) -> bool {
+  return s == "true 5 5
+10
+7 7
+java.lang.UnsupportedOperationException 7
+java.lang.RuntimeException
+1\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "buffers",
+  lib: "buffers",
+  java_class: "Buffers",
+  test_fn: test_buffers,
+}}
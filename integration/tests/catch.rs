@@ -0,0 +1,17 @@
+use super::IntegrationTest;
+
+fn test_catch(s: String) -> bool {
+  return s == "unmatched java.io.EOFException 1 false
+no exception
+file not found: config.txt
+other io: java.io.EOFException
+unhandled java.lang.IllegalArgumentException
+rethrown invalid\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "catch",
+  lib: "catch",
+  java_class: "Catch",
+  test_fn: test_catch,
+}}
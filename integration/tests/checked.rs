@@ -0,0 +1,16 @@
+use super::IntegrationTest;
+
+fn test_checked(s: String) -> bool {
+  return s == "4
+Invalid use of static int length(java.lang.String) in Checked: argument 1: void is not a value
+Invalid use of static int parse(java.lang.Integer) in Checked: argument 1: expected java.lang.Integer, got a java.lang.String
+Invalid use of static int missing in Checked: a Java exception is pending
+pending\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "checked",
+  lib: "checked",
+  java_class: "Checked",
+  test_fn: test_checked,
+}}
@@ -0,0 +1,15 @@
+use super::IntegrationTest;
+
+fn test_constants(s: String) -> bool {
+  return s == "ENABLED HIGH_SURROGATE MAGIC NAME RATIO SEPARATOR VERSION
+Protocol { version: 3, magic_number: 3405691582, name: \"jnat\", separator: ':', enabled: true, ratio: 0.5 }
+MISSING I
+Char 0xd800 is a surrogate\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "constants",
+  lib: "constants",
+  java_class: "Constants",
+  test_fn: test_constants,
+}}
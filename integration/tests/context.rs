@@ -0,0 +1,16 @@
+use super::IntegrationTest;
+
+fn test_context(s: String) -> bool {
+  return s == "Invalid use of static int twice(int) in Context: expected 1 arguments, got 0
+Invalid use of int count in Context: expected int, got long
+static L; twice(int) in Context: Parse failed for input: (I)L;
+true
+void absent() in Context: Method not found: absent ()V\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "context",
+  lib: "context",
+  java_class: "Context",
+  test_fn: test_context,
+}}
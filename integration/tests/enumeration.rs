@@ -0,0 +1,18 @@
+use super::IntegrationTest;
+
+fn test_enumeration(s: String) -> bool {
+  return s == "1 GREEN\n\
+    Green\n\
+    Red Green DarkBlue unknown \n\
+    Expected an instance of Color, got java.lang.Class\n\
+    true false\n\
+    java.lang.NoClassDefFoundError: Shade\n\
+    DARK_BLUE\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "enumeration",
+  lib: "enumeration",
+  java_class: "Enumeration",
+  test_fn: test_enumeration,
+}}
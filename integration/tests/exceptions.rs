@@ -0,0 +1,16 @@
+use super::IntegrationTest;
+
+fn test_exceptions(s: String) -> bool {
+  return s == "java.lang.IllegalStateException: outer
+java.io.IOException: inner
+Exceptions.fail
+1
+rethrown outer\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "exceptions",
+  lib: "exceptions",
+  java_class: "Exceptions",
+  test_fn: test_exceptions,
+}}
@@ -0,0 +1,15 @@
+use super::IntegrationTest;
+
+fn test_features(s: String) -> bool {
+  return s == "true
+java.base
+false
+true\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "features",
+  lib: "features",
+  java_class: "Features",
+  test_fn: test_features,
+}}
@@ -0,0 +1,12 @@
+use super::IntegrationTest;
+
+fn test_initialization(s: String) -> bool {
+  return s == "found\ninitialized\nboolean\nint\nvoid\njava.lang.String\n[D\n[[Ljava.lang.String;\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "initialization",
+  lib: "initialization",
+  java_class: "Initialization",
+  test_fn: test_initialization,
+}}
@@ -0,0 +1,13 @@
+use super::IntegrationTest;
+
+fn test_java_string(s: String) -> bool {
+  return s == "5 hello e el Less true false Index 5 out of bounds for length 5 Hello, el!
+true\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "java_string",
+  lib: "java_string",
+  java_class: "JavaStrings",
+  test_fn: test_java_string,
+}}
@@ -67,6 +67,16 @@ fn method(env: JNIEnv, _: JClass) {
   short_array.set(0, 1).unwrap();
   print_array(&short_array);
   println!();
+
+  println!("{}", int_array.get(10).unwrap_err());
+  println!("{}", int_array.set(usize::MAX, 1).unwrap_err());
+  println!(
+    "{}",
+    env
+      .new_object_array(0, "java/lang/String")
+      .get(0)
+      .unwrap_err()
+  );
 }
 
 fn print_array<'a, T: Display, J>(arr: &impl Array<'a, T, J>) {
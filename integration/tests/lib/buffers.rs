@@ -0,0 +1,47 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{
+    objects::{JByteBuffer, JClass},
+    sys::{jbyte, jint, jobject},
+    JNIEnv,
+  },
+  DirectBuffer, Env,
+};
+
+jnat!(Buffers, shared, (JNIEnv, JClass, jint) -> jnat::Result<jobject>);
+jnat!(Buffers, sum, (JNIEnv, JClass, JByteBuffer) -> jnat::Result<jint>);
+jnat!(Buffers, fill, (JNIEnv, JClass, JByteBuffer, jbyte) -> jnat::Result<()>);
+jnat!(Buffers, released, (JNIEnv, JClass) -> jnat::Result<jint>);
+
+fn shared(env: JNIEnv, _: JClass, length: jint) -> jnat::Result<jobject> {
+  let env = Env::new(&env);
+  let memory = (1..=length as u8).collect::<Vec<u8>>();
+
+  let buffer = DirectBuffer::from_memory(&env, memory)?;
+  Ok(buffer.get_object().as_raw())
+}
+
+fn sum(env: JNIEnv, _: JClass, buffer: JByteBuffer) -> jnat::Result<jint> {
+  let env = Env::new(&env);
+  let buffer = DirectBuffer::new(&env, buffer);
+
+  // Java does not use the buffer during the call
+  let bytes = unsafe { buffer.as_slice()? };
+  Ok(bytes.iter().map(|b| *b as jint).sum())
+}
+
+fn fill(env: JNIEnv, _: JClass, buffer: JByteBuffer, value: jbyte) -> jnat::Result<()> {
+  let env = Env::new(&env);
+  let mut buffer = DirectBuffer::new(&env, buffer);
+
+  // Java does not use the buffer during the call
+  unsafe { buffer.as_mut_slice()? }.fill(value as u8);
+  Ok(())
+}
+
+fn released(env: JNIEnv, _: JClass) -> jnat::Result<jint> {
+  let env = Env::new(&env);
+  Ok(DirectBuffer::release_unreachable(&env)? as jint)
+}
@@ -0,0 +1,71 @@
+extern crate jnat;
+
+use jnat::{
+  catch,
+  jnat_macros::jnat,
+  jni::{
+    objects::{JClass, JObjectArray},
+    JNIEnv,
+  },
+  Env, Error, Signature, Type,
+};
+
+jnat!(Catch, method, (JNIEnv, JClass) -> jnat::Result<()>);
+
+fn method(env: JNIEnv, _: JClass) -> jnat::Result<()> {
+  let env = Env::new(&env);
+  let class = env.get_class("Catch").unwrap();
+
+  let result = class
+    .call_static_method("truncated", Signature::new(&[], Type::Void), &[])
+    .map(|_| ());
+  match catch!(&env, result, { "Missing" => |_| () }) {
+    Err(Error::Exception(e)) => {
+      let suppressed = env
+        .get_jni_env()
+        .call_method(
+          e.get_throwable(),
+          "getSuppressed",
+          "()[Ljava/lang/Throwable;",
+          &[],
+        )
+        .unwrap()
+        .l()
+        .unwrap();
+      let suppressed = env
+        .get_jni_env()
+        .get_array_length(&JObjectArray::from(suppressed))
+        .unwrap();
+
+      println!(
+        "unmatched {} {} {}",
+        e.class,
+        suppressed,
+        env.get_jni_env().exception_check().unwrap()
+      );
+    }
+    _ => unreachable!(),
+  }
+
+  for name in ["succeed", "missing", "truncated", "invalid"] {
+    let result = class
+      .call_static_method(name, Signature::new(&[], Type::Void), &[])
+      .map(|_| "no exception".to_string());
+
+    let handled = catch!(&env, result, {
+      "java/io/FileNotFoundException" => |e| format!("file not found: {}", e.message.unwrap()),
+      "java/io/IOException" => |e| format!("other io: {}", e.class),
+    });
+
+    match handled {
+      Ok(message) => println!("{}", message),
+      Err(Error::Exception(e)) => {
+        println!("unhandled {}", e.class);
+        return Err(Error::Exception(e));
+      }
+      Err(_) => unreachable!(),
+    }
+  }
+
+  Ok(())
+}
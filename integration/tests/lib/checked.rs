@@ -0,0 +1,52 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{
+    objects::{JClass, JObject},
+    JNIEnv,
+  },
+  Class, Env, Object, Signature, Type, Value,
+};
+
+jnat!(Checked, method, (JNIEnv, JClass, JObject) -> ());
+
+fn method(env: JNIEnv, class: JClass, string: JObject) {
+  let env = Env::new(&env);
+  let class = Class::new(&env, class);
+  let string = Object::new(&env, &string);
+
+  let length = class
+    .call_static_method(
+      "length",
+      Signature::new(&[Type::Object("java/lang/String")], Type::Int),
+      &[Value::Object(string)],
+    )
+    .unwrap();
+  println!("{}", length.i().unwrap());
+
+  let error = class
+    .call_static_method(
+      "length",
+      Signature::new(&[Type::Object("java/lang/String")], Type::Int),
+      &[Value::Void],
+    )
+    .unwrap_err();
+  println!("{}", error);
+
+  let error = class
+    .call_static_method(
+      "parse",
+      Signature::new(&[Type::Object("java/lang/Integer")], Type::Int),
+      &[Value::Object(string)],
+    )
+    .unwrap_err();
+  println!("{}", error);
+
+  env
+    .throw_new("java/lang/IllegalStateException", "pending")
+    .unwrap();
+
+  let error = class.get_static_field("missing", Type::Int).unwrap_err();
+  println!("{}", error);
+}
@@ -0,0 +1,57 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::{jnat, JavaConstants},
+  jni::{errors, objects::JClass, JNIEnv},
+  Env, Error, JavaConstants,
+};
+
+#[derive(Debug, JavaConstants)]
+#[jnat(class = "Constants")]
+struct Protocol {
+  version: i32,
+  #[jnat(name = "MAGIC")]
+  magic_number: i64,
+  name: String,
+  separator: char,
+  enabled: bool,
+  ratio: f64,
+}
+
+#[derive(Debug, JavaConstants)]
+#[jnat(class = "Constants")]
+struct Missing {
+  #[allow(dead_code)]
+  missing: i32,
+}
+
+#[derive(Debug, JavaConstants)]
+#[jnat(class = "Constants")]
+struct Surrogate {
+  #[allow(dead_code)]
+  high_surrogate: char,
+}
+
+jnat!(Constants, method, (JNIEnv, JClass) -> ());
+
+fn method(env: JNIEnv, _: JClass) {
+  let env = Env::new(&env);
+  let class = env.get_class("Constants").unwrap();
+
+  let mut names = class
+    .constants()
+    .unwrap()
+    .into_keys()
+    .collect::<Vec<String>>();
+  names.sort();
+  println!("{}", names.join(" "));
+
+  println!("{:?}", Protocol::load(&env).unwrap());
+
+  match Missing::load(&env) {
+    Err(Error::Jni(errors::Error::FieldNotFound { name, sig })) => println!("{} {}", name, sig),
+    _ => unreachable!(),
+  }
+
+  println!("{}", Surrogate::load(&env).unwrap_err());
+}
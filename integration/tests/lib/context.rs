@@ -0,0 +1,44 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{
+    objects::{JClass, JObject},
+    JNIEnv,
+  },
+  Class, Env, Object, Signature, Type, Value,
+};
+use std::error::Error;
+
+jnat!(Context, method, (JNIEnv, JClass, JObject) -> ());
+
+fn method(env: JNIEnv, class: JClass, context: JObject) {
+  let env = Env::new(&env);
+  let class = Class::new(&env, class);
+  let context = Object::new(&env, &context);
+
+  let error = class
+    .call_static_method("twice", Signature::new(&[Type::Int], Type::Int), &[])
+    .unwrap_err();
+  println!("{}", error);
+
+  let error = context
+    .set_field("count", Type::Int, Value::Long(2))
+    .unwrap_err();
+  println!("{}", error);
+
+  let error = class
+    .call_static_method(
+      "twice",
+      Signature::new(&[Type::Int], Type::Object("")),
+      &[Value::Int(1)],
+    )
+    .unwrap_err();
+  println!("{}", error);
+  println!("{}", error.source().is_some());
+
+  let error = context
+    .call_method("absent", Signature::new(&[], Type::Void), &[])
+    .unwrap_err();
+  println!("{}", error);
+}
@@ -0,0 +1,68 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::{jnat, JavaEnum},
+  jni::{
+    objects::{JClass, JObject},
+    JNIEnv,
+  },
+  Env, JavaEnum, Object, Signature, Type, Value,
+};
+
+#[derive(Debug, Clone, Copy, JavaEnum)]
+#[jnat(class = "Color")]
+enum Color {
+  Red,
+  Green,
+  DarkBlue,
+}
+
+#[derive(Debug, JavaEnum)]
+#[jnat(class = "Shade")]
+enum Shade {
+  Light,
+}
+
+jnat!(Enumeration, method, (JNIEnv, JClass, JObject) -> ());
+
+fn method(env: JNIEnv, _: JClass, color: JObject) {
+  let env = Env::new(&env);
+  let color = Object::new(&env, &color);
+
+  println!(
+    "{} {}",
+    color.enum_ordinal().unwrap(),
+    color.enum_name().unwrap()
+  );
+  println!("{:?}", Color::from_java(&color).unwrap());
+
+  let class = env.get_class("Color").unwrap();
+  for constant in class.enum_constants().unwrap() {
+    match Color::from_java(&Object::new(&env, &constant)) {
+      Ok(color) => print!("{:?} ", color),
+      Err(_) => print!("unknown "),
+    }
+  }
+  println!();
+
+  let class = class.get_class();
+  let class_object = Object::new(&env, &class);
+  println!("{}", Color::from_java(&class_object).unwrap_err());
+  println!(
+    "{} {}",
+    class_object.enum_name().is_err(),
+    env.get_jni_env().exception_check().unwrap()
+  );
+  println!("{}", Shade::Light.to_java(&env).unwrap_err());
+
+  let dark_blue = Color::DarkBlue.to_java(&env).unwrap();
+  env
+    .get_class("Enumeration")
+    .unwrap()
+    .call_static_method(
+      "print",
+      Signature::new(&[Type::Object("Color")], Type::Void),
+      &[Value::Object(Object::new(&env, &dark_blue))],
+    )
+    .unwrap();
+}
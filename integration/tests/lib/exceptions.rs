@@ -0,0 +1,34 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{objects::JClass, JNIEnv},
+  Env, Error, Signature, Type,
+};
+
+jnat!(Exceptions, method, (JNIEnv, JClass) -> ());
+
+fn method(env: JNIEnv, _: JClass) {
+  let env = Env::new(&env);
+  let class = env.get_class("Exceptions").unwrap();
+
+  let exception = match class.call_static_method("fail", Signature::new(&[], Type::Void), &[]) {
+    Err(Error::Exception(exception)) => exception,
+    _ => unreachable!(),
+  };
+
+  println!("{}", exception);
+  println!("{}", exception.cause.as_ref().unwrap());
+  println!(
+    "{}.{}",
+    exception.stack_trace[0].class, exception.stack_trace[0].method
+  );
+
+  // The exception was cleared, so further calls succeed
+  let value = class
+    .call_static_method("succeed", Signature::new(&[], Type::Int), &[])
+    .unwrap();
+  println!("{}", value.i().unwrap());
+
+  exception.rethrow(&env).unwrap();
+}
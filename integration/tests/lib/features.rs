@@ -0,0 +1,56 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{
+    objects::{JClass, JObject},
+    sys::{jboolean, jstring},
+    JNIEnv,
+  },
+  Class, Env, Object, Signature, Type, Version,
+};
+
+jnat!(Features, atLeast9, (JNIEnv, JClass) -> jboolean);
+jnat!(Features, module, (JNIEnv, JClass, JClass) -> jnat::Result<jstring>);
+jnat!(Features, isVirtual, (JNIEnv, JClass, JObject) -> jnat::Result<jboolean>);
+jnat!(Features, unsupported, (JNIEnv, JClass) -> jboolean);
+
+#[allow(non_snake_case)]
+fn atLeast9(env: JNIEnv, _: JClass) -> jboolean {
+  let env = Env::new(&env);
+  (env.version() >= Version::V9) as jboolean
+}
+
+fn module(env: JNIEnv, _: JClass, class: JClass) -> jnat::Result<jstring> {
+  let env = Env::new(&env);
+  let class = Class::new(&env, class);
+
+  let module = env.get_module(&class)?;
+  let module = Object::new(&env, &module);
+  let name = module.call_method(
+    "getName",
+    Signature::new(&[], Type::Object("java/lang/String")),
+    &[],
+  )?;
+
+  Ok(name.l()?.into_raw())
+}
+
+#[allow(non_snake_case)]
+fn isVirtual(env: JNIEnv, _: JClass, thread: JObject) -> jnat::Result<jboolean> {
+  let env = Env::new(&env);
+  Ok(env.is_virtual_thread(&Object::new(&env, &thread))? as jboolean)
+}
+
+fn unsupported(env: JNIEnv, _: JClass) -> jboolean {
+  let env = Env::new(&env);
+  let required = Version::from_raw(env.version().to_raw() + 1);
+
+  matches!(
+    env.require("Future", required),
+    Err(jnat::Error::Unsupported {
+      function: "Future",
+      ..
+    })
+  ) as jboolean
+}
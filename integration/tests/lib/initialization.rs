@@ -0,0 +1,42 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{objects::JClass, JNIEnv},
+  Class, Env, Type,
+};
+
+jnat!(Initialization, method, (JNIEnv, JClass, JClass) -> ());
+
+fn method(env: JNIEnv, _: JClass, lazy: JClass) {
+  let env = Env::new(&env);
+
+  let lazy = Class::new(&env, lazy);
+  println!("found");
+  lazy.ensure_initialized().unwrap();
+  lazy.ensure_initialized().unwrap();
+
+  for r#type in [
+    Type::Boolean,
+    Type::Int,
+    Type::Void,
+    Type::Object("java/lang/String"),
+    Type::Array(&Type::Double),
+    Type::Array(&Type::Array(&Type::Object("java/lang/String"))),
+  ] {
+    let class = env.class_for_type(r#type).unwrap();
+    class.ensure_initialized().unwrap();
+    println!("{}", name(&env, class));
+  }
+}
+
+fn name(env: &Env, class: Class) -> String {
+  let mut jni_env = env.get_jni_env();
+  let name = jni_env
+    .call_method(class.get_class(), "getName", "()Ljava/lang/String;", &[])
+    .unwrap()
+    .l()
+    .unwrap();
+
+  jni_env.get_string(&name.into()).unwrap().into()
+}
@@ -0,0 +1,61 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{
+    objects::{JClass, JString},
+    sys::jstring,
+    JNIEnv,
+  },
+  Env, JavaString, ReturnValue, Signature, Type, Value,
+};
+
+jnat!(JavaStrings, describe, (JNIEnv, JClass, JString, JString) -> jnat::Result<jstring>);
+jnat!(JavaStrings, intern, (JNIEnv, JClass, JString) -> jnat::Result<jstring>);
+
+fn describe(env: JNIEnv, class: JClass, s: JString, other: JString) -> jnat::Result<jstring> {
+  let env = Env::new(&env);
+  let s = JavaString::new(&env, s);
+  let other = JavaString::new(&env, other);
+
+  let substring = s.substring(1, 3)?;
+  let out_of_bounds = match s.char_at(5) {
+    Ok(c) => c.to_string(),
+    Err(error) => error.to_string(),
+  };
+
+  let class = jnat::Class::new(&env, class);
+  let greeting = match class.call_static_method_typed(
+    "greet",
+    Signature::new(
+      &[Type::Object("java/lang/String")],
+      Type::Object("java/lang/String"),
+    ),
+    &[Value::String(&substring)],
+  )? {
+    ReturnValue::String(greeting) => greeting.to_string()?,
+    _ => "not a string".to_string(),
+  };
+
+  let description = format!(
+    "{} {} {} {} {:?} {} {} {} {}",
+    s.len_utf16()?,
+    s.to_string()?,
+    s.char_at(1)? as u8 as char,
+    substring.to_string()?,
+    s.compare(&other)?,
+    s.equals_str("hello")?,
+    s.equals_str("hell")?,
+    out_of_bounds,
+    greeting,
+  );
+
+  Ok(env.new_java_string(&description)?.get_string().as_raw())
+}
+
+fn intern(env: JNIEnv, _: JClass, s: JString) -> jnat::Result<jstring> {
+  let env = Env::new(&env);
+  let interned = JavaString::new(&env, s).intern()?;
+
+  Ok(interned.get_string().as_raw())
+}
@@ -0,0 +1,38 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{
+    objects::{JClass, JObject},
+    sys::jstring,
+    JNIEnv,
+  },
+  on_load, on_unload, Env, JavaVm, Object, Signature, Type, Value,
+};
+
+#[on_load(version = "9")]
+fn load(env: Env) -> jnat::Result<()> {
+  let message = format!("loaded, stored {}", JavaVm::global().is_some());
+  let message = env.new_string(&message)?;
+
+  env.get_class("Load")?.call_static_method(
+    "loaded",
+    Signature::new(&[Type::Object("java/lang/String")], Type::Void),
+    &[Value::Object(Object::new(&env, &message))],
+  )?;
+
+  Ok(())
+}
+
+#[on_unload]
+fn unload(_: Env) {}
+
+jnat!(Load, current, (JNIEnv, JClass) -> jstring);
+
+fn current(_: JNIEnv, _: JClass) -> jstring {
+  // The JVM was stored when the library was loaded
+  let env = Env::current().unwrap();
+  let version = env.new_string("current").unwrap();
+
+  JObject::from(version).into_raw()
+}
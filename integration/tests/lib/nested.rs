@@ -0,0 +1,66 @@
+extern crate jnat;
+
+use jnat::{
+  anonymous_class_name,
+  jnat_macros::jnat,
+  jni::{
+    objects::{JClass, JObject},
+    JNIEnv,
+  },
+  local_class_name, Env, Object, Signature, Type, Value,
+};
+
+jnat!(Nested, method, (JNIEnv, JClass, JObject) -> ());
+
+fn method(env: JNIEnv, _: JClass, outer: JObject) {
+  let env = Env::new(&env);
+  let outer = Object::new(&env, &outer);
+  let suffix = env.new_string("inner").unwrap();
+
+  env
+    .get_nested_class("Nested.Inner")
+    .unwrap()
+    .create_inner(
+      &outer,
+      Signature::new(&[Type::Object("java/lang/String"), Type::Int], Type::Void),
+      &[Value::Object(Object::new(&env, &suffix)), Value::Int(1)],
+    )
+    .unwrap();
+
+  env
+    .get_class(&local_class_name("Nested", 1, "Local"))
+    .unwrap()
+    .create_inner(&outer, Signature::new(&[], Type::Void), &[])
+    .unwrap();
+
+  env
+    .get_class(&anonymous_class_name("Nested", 1))
+    .unwrap()
+    .create_inner(&outer, Signature::new(&[], Type::Void), &[])
+    .unwrap();
+
+  env
+    .get_nested_class("Nested.StaticNested")
+    .unwrap()
+    .create_inner(
+      &outer,
+      Signature::new(&[Type::Int], Type::Void),
+      &[Value::Int(2)],
+    )
+    .unwrap();
+
+  env
+    .get_class(&local_class_name("Nested", 1, "StaticLocal"))
+    .unwrap()
+    .create_inner(&outer, Signature::new(&[], Type::Void), &[])
+    .unwrap();
+
+  match env
+    .get_class("Nested")
+    .unwrap()
+    .create_inner(&outer, Signature::new(&[], Type::Void), &[])
+  {
+    Err(error @ jnat::Error::Check { .. }) => println!("{}", error),
+    _ => unreachable!(),
+  }
+}
@@ -6,7 +6,7 @@ use jnat::{
     objects::{JClass, JString},
     JNIEnv,
   },
-  Array, Env, ObjectArray,
+  Array, Env, Error, ObjectArray,
 };
 
 jnat!(ObjectArray, method, (JNIEnv, JClass) -> ());
@@ -30,6 +30,15 @@ fn method(env: JNIEnv, _: JClass) {
 
   print_array(env, &object_array);
   println!();
+
+  match env.try_new_object_array(1, "com/example/Missing") {
+    Ok(_) => println!("created"),
+    Err(Error::Exception(exception)) => println!("{}", exception.class),
+    Err(error) => println!("{}", error),
+  }
+
+  let object_array = env.try_new_object_array(2, "java/lang/String").unwrap();
+  println!("{}", object_array.try_length().unwrap());
 }
 
 fn print_array<'a>(env: Env, arr: &ObjectArray<'a>) {
@@ -0,0 +1,34 @@
+extern crate jnat;
+
+use jnat::{
+  install_panic_hook,
+  jnat_macros::jnat,
+  jni::{
+    objects::JClass,
+    sys::{jint, jstring},
+    JNIEnv,
+  },
+  on_load, Env,
+};
+
+// Rust frames are only added to the stack trace once the hook is installed
+#[on_load]
+fn load(_: Env) {
+  install_panic_hook();
+}
+
+jnat!(Panic, divide, (JNIEnv, JClass, jint, jint) -> jint);
+jnat!(Panic, name, (JNIEnv, JClass, jint) -> jstring, panic = "java/lang/IllegalStateException");
+
+fn divide(_: JNIEnv, _: JClass, a: jint, b: jint) -> jint {
+  if b == 0 {
+    panic!("cannot divide {} by zero", a);
+  }
+
+  a / b
+}
+
+fn name(_: JNIEnv, _: JClass, index: jint) -> jstring {
+  let names = ["zero", "one"];
+  panic!("no name at {}", names.len() + index as usize);
+}
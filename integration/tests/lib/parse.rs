@@ -0,0 +1,45 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{
+    objects::{JClass, JString},
+    sys::jint,
+    JNIEnv,
+  },
+  Class, Env, JavaThrowable, Signature, Type,
+};
+use std::fmt::{self, Display, Formatter};
+
+struct ParseError(String);
+
+impl Display for ParseError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "not a number: {}", self.0)
+  }
+}
+
+impl JavaThrowable for ParseError {
+  fn java_class(&self) -> &str {
+    "java/lang/NumberFormatException"
+  }
+}
+
+jnat!(Parse, parse, (JNIEnv, JClass, JString) -> Result<jint, ParseError>);
+jnat!(Parse, missing, (JNIEnv, JClass) -> jnat::Result<jint>);
+
+fn parse(env: JNIEnv, _: JClass, s: JString) -> Result<jint, ParseError> {
+  let env = Env::new(&env);
+  let s = env.get_string(s).unwrap();
+
+  s.parse().map_err(|_| ParseError(s))
+}
+
+fn missing(env: JNIEnv, class: JClass) -> jnat::Result<jint> {
+  let env = Env::new(&env);
+  let class = Class::new(&env, class);
+
+  let value = class.call_static_method("absent", Signature::new(&[], Type::Int), &[])?;
+
+  Ok(value.i()?)
+}
@@ -0,0 +1,92 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{
+    errors,
+    objects::{JClass, JObject, JString},
+    JNIEnv,
+  },
+  Env, Error, Object, Signature, Type, Value,
+};
+
+jnat!(Reflection, method, (JNIEnv, JClass, JObject, JObject) -> ());
+
+fn method(env: JNIEnv, _: JClass, instance: JObject, string: JObject) {
+  let env = Env::new(&env);
+  let instance = Object::new(&env, &instance);
+
+  println!(
+    "{}",
+    instance.get_field_dynamic("secret").unwrap().i().unwrap()
+  );
+  println!(
+    "{}",
+    instance
+      .get_field_dynamic("inherited")
+      .unwrap()
+      .j()
+      .unwrap()
+  );
+
+  let label = instance.get_field_dynamic("label").unwrap().l().unwrap();
+  println!("{}", env.get_string(JString::from(label)).unwrap());
+
+  let name = env.new_string("greeting").unwrap();
+  let greeting = instance
+    .invoke_private(
+      "greet",
+      Signature::new(
+        &[Type::Object("java/lang/String"), Type::Int],
+        Type::Object("java/lang/String"),
+      ),
+      &[Value::Object(Object::new(&env, &name)), Value::Int(2)],
+    )
+    .unwrap()
+    .l()
+    .unwrap();
+  println!("{}", env.get_string(JString::from(greeting)).unwrap());
+
+  let twice = instance
+    .invoke_private(
+      "twice",
+      Signature::new(&[Type::Int], Type::Int),
+      &[Value::Int(21)],
+    )
+    .unwrap();
+  println!("{}", twice.i().unwrap());
+
+  instance
+    .invoke_private("hidden", Signature::new(&[], Type::Void), &[])
+    .unwrap();
+
+  match instance.get_field_dynamic("missing") {
+    Err(Error::Jni(errors::Error::FieldNotFound { name, .. })) => println!("not found {}", name),
+    _ => unreachable!(),
+  }
+
+  match instance.invoke_private(
+    "twice",
+    Signature::new(&[Type::Long], Type::Int),
+    &[Value::Long(1)],
+  ) {
+    Err(Error::Jni(errors::Error::MethodNotFound { name, sig })) => {
+      println!("not found {} {}", name, sig)
+    }
+    _ => unreachable!(),
+  }
+
+  match instance.invoke_private(
+    "twice",
+    Signature::new(&[Type::Int], Type::Int),
+    &[Value::Long(1)],
+  ) {
+    Err(error @ Error::Check { .. }) => println!("{}", error),
+    _ => unreachable!(),
+  }
+
+  match Object::new(&env, &string).get_field_dynamic("value") {
+    Err(Error::Inaccessible { class, member, .. }) => println!("inaccessible {}.{}", class, member),
+    _ => unreachable!(),
+  }
+}
@@ -0,0 +1,51 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{
+    objects::{JClass, JString},
+    sys::jstring,
+    JNIEnv,
+  },
+  Env,
+};
+
+jnat!(Strings, describe, (JNIEnv, JClass, JString) -> jnat::Result<jstring>);
+jnat!(Strings, roundTrip, (JNIEnv, JClass, JString) -> jnat::Result<jstring>);
+
+fn describe(env: JNIEnv, _: JClass, s: JString) -> jnat::Result<jstring> {
+  let env = Env::new(&env);
+
+  let (length, ascii, lossy) = {
+    let chars = env.get_string_chars(&s)?;
+    (chars.len(), chars.is_ascii(), chars.to_string_lossy())
+  };
+  let critical = {
+    let chars = env.get_string_critical(&s)?;
+    chars.iter().filter(|unit| **unit >= 0x80).count()
+  };
+  let strict = match env.get_string_strict(&s) {
+    Ok(strict) => strict,
+    Err(error) => error.to_string(),
+  };
+  let string = env.get_string(s)?;
+
+  let description = format!(
+    "{} {} {} \"{}\" \"{}\" \"{}\"",
+    length,
+    ascii,
+    critical,
+    lossy.escape_default(),
+    strict.escape_default(),
+    string.escape_default()
+  );
+  Ok(env.new_string(&description)?.into_raw())
+}
+
+#[allow(non_snake_case)]
+fn roundTrip(env: JNIEnv, _: JClass, s: JString) -> jnat::Result<jstring> {
+  let env = Env::new(&env);
+  let chars = env.get_string_chars(&s)?.to_vec();
+
+  Ok(env.new_string_utf16(&chars)?.into_raw())
+}
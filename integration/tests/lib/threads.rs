@@ -0,0 +1,61 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{objects::JClass, JNIEnv},
+  Env, JavaVm, Object, Signature, Type, Value,
+};
+use std::thread;
+
+jnat!(Threads, run, (JNIEnv, JClass) -> ());
+
+fn record(env: &Env, result: &str) {
+  let class = env.get_class("Threads").unwrap();
+  let result = env.new_string(result).unwrap();
+
+  class
+    .call_static_method(
+      "record",
+      Signature::new(&[Type::Object("java/lang/String")], Type::Void),
+      &[Value::Object(Object::new(env, &result))],
+    )
+    .unwrap();
+}
+
+fn run(env: JNIEnv, _: JClass) {
+  let env = Env::new(&env);
+  JavaVm::from_env(&env).unwrap().store();
+
+  // The Java thread calling the native method is already attached
+  record(&Env::current().unwrap(), "java");
+
+  let threads = [
+    thread::spawn(|| record(&Env::current().unwrap(), "current")),
+    thread::spawn(|| {
+      let vm = JavaVm::global().unwrap();
+
+      let guard = vm.attach_current_thread().unwrap();
+      record(&Env::new(&guard), "guard");
+      drop(guard);
+
+      let detached = vm.get_jni_env().is_err();
+      record(&Env::current().unwrap(), &format!("detached {}", detached));
+    }),
+    thread::spawn(|| {
+      let guard = JavaVm::global().unwrap().attach_current_thread().unwrap();
+      Env::current().unwrap();
+      // The guard detaches the thread, so the stored environment must not be reused
+      drop(guard);
+
+      record(&Env::current().unwrap(), "reattached");
+    }),
+    thread::spawn(|| {
+      let env = JavaVm::global().unwrap().attach_as_daemon().unwrap();
+      record(&env, "daemon");
+    }),
+  ];
+
+  for thread in threads {
+    thread.join().unwrap();
+  }
+}
@@ -0,0 +1,80 @@
+extern crate jnat;
+
+use jnat::{
+  jnat_macros::jnat,
+  jni::{
+    objects::{JClass, JObject},
+    sys::jint,
+    JNIEnv,
+  },
+  Env, JavaThrowable, Object,
+};
+use std::{
+  backtrace::Backtrace,
+  fmt::{self, Display, Formatter},
+};
+
+struct ParseError(&'static str);
+
+impl Display for ParseError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "cannot parse {}", self.0)
+  }
+}
+
+impl JavaThrowable for ParseError {
+  fn java_class(&self) -> &str {
+    "java/lang/IllegalArgumentException"
+  }
+}
+
+struct TracedError(Backtrace);
+
+impl Display for TracedError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "traced")
+  }
+}
+
+impl JavaThrowable for TracedError {
+  fn java_class(&self) -> &str {
+    "java/lang/IllegalStateException"
+  }
+
+  fn backtrace(&self) -> Option<&Backtrace> {
+    Some(&self.0)
+  }
+}
+
+jnat!(Throw, method, (JNIEnv, JClass, jint, JObject) -> ());
+
+fn method(env: JNIEnv, _: JClass, scenario: jint, existing: JObject) {
+  let env = Env::new(&env);
+  let existing = Object::new(&env, &existing);
+
+  match scenario {
+    0 => env.throw_new("java/io/IOException", "new").unwrap(),
+    1 => env
+      .throwable("java/lang/IllegalStateException")
+      .message("built")
+      .cause(existing)
+      .throw()
+      .unwrap(),
+    2 => ParseError("abc").throw(&env).unwrap(),
+    3 => TracedError(Backtrace::force_capture()).throw(&env).unwrap(),
+    _ => {
+      let string = env.new_string("not a throwable").unwrap();
+      println!("{}", env.throw(&Object::new(&env, &string)).unwrap_err());
+      println!(
+        "{}",
+        env
+          .throwable("java/lang/IllegalStateException")
+          .cause(Object::new(&env, &string))
+          .build()
+          .unwrap_err()
+      );
+
+      env.throw(&existing).unwrap()
+    }
+  }
+}
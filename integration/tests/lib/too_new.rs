@@ -0,0 +1,7 @@
+extern crate jnat;
+
+use jnat::{on_load, Env};
+
+// No Java version before 24 supports JNI 24
+#[on_load(version = "24")]
+fn load(_: Env) {}
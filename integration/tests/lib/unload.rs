@@ -0,0 +1,27 @@
+extern crate jnat;
+
+use jnat::{on_unload, Env, Object, Signature, Type, Value};
+
+#[on_unload]
+fn unload(env: Env) -> jnat::Result<()> {
+  // The unloading thread may not find application classes, so a system property is set
+  let key = env.new_string("unloaded")?;
+  let value = env.new_string("true")?;
+
+  env.get_class("java/lang/System")?.call_static_method(
+    "setProperty",
+    Signature::new(
+      &[
+        Type::Object("java/lang/String"),
+        Type::Object("java/lang/String"),
+      ],
+      Type::Object("java/lang/String"),
+    ),
+    &[
+      Value::Object(Object::new(&env, &key)),
+      Value::Object(Object::new(&env, &value)),
+    ],
+  )?;
+
+  Ok(())
+}
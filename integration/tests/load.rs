@@ -0,0 +1,13 @@
+use super::IntegrationTest;
+
+fn test_load(s: String) -> bool {
+  return s == "loaded, stored true
+current\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "load",
+  lib: "load",
+  java_class: "Load",
+  test_fn: test_load,
+}}
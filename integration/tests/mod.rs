@@ -1,10 +1,30 @@
 pub mod array;
+pub mod buffers;
+pub mod catch;
+pub mod checked;
+pub mod constants;
+pub mod context;
+pub mod enumeration;
+pub mod exceptions;
+pub mod features;
 pub mod field;
 pub mod hello;
+pub mod initialization;
+pub mod java_string;
+pub mod load;
 pub mod method;
+pub mod nested;
 pub mod object_array;
+pub mod panic;
+pub mod parse;
+pub mod reflection;
 pub mod static_field;
 pub mod static_method;
+pub mod strings;
+pub mod threads;
+pub mod throw;
+pub mod too_new;
+pub mod unload;
 
 #[derive(Debug)]
 pub struct IntegrationTest {
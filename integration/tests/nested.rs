@@ -0,0 +1,15 @@
+use super::IntegrationTest;
+
+fn test_nested(s: String) -> bool {
+  return s == "outer inner 1\nouter local\nouter anonymous\n\
+    static nested 2\n\
+    static local\n\
+    Invalid use of Nested() in Nested: a top-level class has no enclosing instance\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "nested",
+  lib: "nested",
+  java_class: "Nested",
+  test_fn: test_nested,
+}}
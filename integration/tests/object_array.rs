@@ -1,7 +1,9 @@
 use super::IntegrationTest;
 
 fn test_object_array(s: String) -> bool {
-  return s == "nullnullnullnullnullnullnullnullnullnull-hellotesttesttesttesttesttesttesttesttest\n";
+  return s == "nullnullnullnullnullnullnullnullnullnull-hellotesttesttesttesttesttesttesttesttest
+java.lang.NoClassDefFoundError
+2\n";
 }
 
 inventory::submit! {IntegrationTest {
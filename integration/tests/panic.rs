@@ -0,0 +1,20 @@
+use super::IntegrationTest;
+
+fn test_panic(s: String) -> bool {
+  let lines = s.lines().collect::<Vec<&str>>();
+
+  // The Rust frame has a file and line only if debug info is available
+  return lines.len() == 4
+    && lines[0] == "2"
+    && lines[1] == "java.lang.RuntimeException: Rust panic: cannot divide 1 by zero"
+    && lines[2].starts_with("panic.divide(")
+    && lines[2].ends_with(" ... Panic.main(Panic.java:14)")
+    && lines[3] == "java.lang.IllegalStateException: Rust panic: no name at 5";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "panic",
+  lib: "panic",
+  java_class: "Panic",
+  test_fn: test_panic,
+}}
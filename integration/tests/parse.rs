@@ -0,0 +1,14 @@
+use super::IntegrationTest;
+
+fn test_parse(s: String) -> bool {
+  return s == "42
+java.lang.NumberFormatException: not a number: abc
+java.lang.RuntimeException: static int absent() in Parse: Method not found: absent ()I\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "parse",
+  lib: "parse",
+  java_class: "Parse",
+  test_fn: test_parse,
+}}
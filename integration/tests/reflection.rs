@@ -0,0 +1,14 @@
+use super::IntegrationTest;
+
+fn test_reflection(s: String) -> bool {
+  return s == "42\n7\nstatic\ngreeting x2\n42\nhidden\nnot found missing\nnot found twice (J)I
+Invalid use of int twice(int) in Reflection: argument 1: expected int, got long
+inaccessible java.lang.String.value\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "reflection",
+  lib: "reflection",
+  java_class: "Reflection",
+  test_fn: test_reflection,
+}}
@@ -0,0 +1,18 @@
+use super::IntegrationTest;
+
+fn test_strings(s: String) -> bool {
+  return s == r#"0 true 0 "" "" ""
+5 true 0 "hello" "hello" "hello"
+8 false 3 "h\u{e9}llo \u{1f600}" "h\u{e9}llo \u{1f600}" "h\u{e9}llo \u{1f600}"
+3 false 1 "a\u{fffd}b" "Unpaired surrogate 0xd83d at index 1" "a\u{fffd}b"
+4 true 0 "nul\u{0}" "nul\u{0}" "nul\u{0}"
+true
+"#;
+}
+
+inventory::submit! {IntegrationTest {
+  name: "strings",
+  lib: "strings",
+  java_class: "Strings",
+  test_fn: test_strings,
+}}
@@ -0,0 +1,17 @@
+use super::IntegrationTest;
+
+fn test_threads(s: String) -> bool {
+  return s == "current false
+daemon true
+detached true false
+guard false
+java false
+reattached false\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "threads",
+  lib: "threads",
+  java_class: "Threads",
+  test_fn: test_threads,
+}}
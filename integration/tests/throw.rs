@@ -0,0 +1,22 @@
+use super::IntegrationTest;
+
+fn test_throw(s: String) -> bool {
+  let lines = s.lines().collect::<Vec<&str>>();
+
+  // The Rust frame has a file and line only if debug info is available
+  return lines.len() == 7
+    && lines[0] == "java.io.IOException: new"
+    && lines[1] == "java.lang.IllegalStateException: built (caused by existing)"
+    && lines[2] == "java.lang.IllegalArgumentException: cannot parse abc"
+    && lines[3].starts_with("java.lang.IllegalStateException: traced at throw.method(")
+    && lines[4] == "Expected an instance of java.lang.Throwable, got java.lang.String"
+    && lines[5] == "Expected an instance of java.lang.Throwable, got java.lang.String"
+    && lines[6] == "java.lang.UnsupportedOperationException: existing";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "throw",
+  lib: "throw",
+  java_class: "Throw",
+  test_fn: test_throw,
+}}
@@ -0,0 +1,12 @@
+use super::IntegrationTest;
+
+fn test_too_new(s: String) -> bool {
+  return s == "loaded if supported true\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "too_new",
+  lib: "too_new",
+  java_class: "TooNew",
+  test_fn: test_too_new,
+}}
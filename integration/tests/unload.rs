@@ -0,0 +1,12 @@
+use super::IntegrationTest;
+
+fn test_unload(s: String) -> bool {
+  return s == "unloaded true\n";
+}
+
+inventory::submit! {IntegrationTest {
+  name: "unload",
+  lib: "unload",
+  java_class: "Unload",
+  test_fn: test_unload,
+}}
@@ -0,0 +1,179 @@
+use jnat::{Array, Object, Signature, Type, Value};
+use std::{
+  collections::HashMap,
+  fmt::Debug,
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+  },
+};
+use tracing::{
+  field::{Field, Visit},
+  span::{Attributes, Id, Record},
+  Event, Metadata, Subscriber,
+};
+
+mod common;
+
+/// A span recorded by Spans, with its fields formatted
+struct Span {
+  name: &'static str,
+  fields: HashMap<&'static str, String>,
+}
+
+impl Visit for Span {
+  fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+    self.fields.insert(field.name(), format!("{:?}", value));
+  }
+
+  fn record_str(&mut self, field: &Field, value: &str) {
+    self.fields.insert(field.name(), value.to_string());
+  }
+}
+
+/// A subscriber recording every span
+#[derive(Clone, Default)]
+struct Spans {
+  next: Arc<AtomicU64>,
+  spans: Arc<Mutex<HashMap<u64, Span>>>,
+}
+
+impl Spans {
+  /// Gets the fields of the spans with a name, in the order they were created
+  fn named(&self, name: &str) -> Vec<HashMap<&'static str, String>> {
+    let spans = self.spans.lock().unwrap();
+    let mut ids = spans
+      .iter()
+      .filter(|(_, span)| span.name == name)
+      .map(|(id, _)| *id)
+      .collect::<Vec<u64>>();
+    ids.sort();
+
+    ids.iter().map(|id| spans[id].fields.clone()).collect()
+  }
+}
+
+impl Subscriber for Spans {
+  fn enabled(&self, _: &Metadata) -> bool {
+    true
+  }
+
+  fn new_span(&self, attributes: &Attributes) -> Id {
+    let id = self.next.fetch_add(1, Ordering::SeqCst) + 1;
+    let mut span = Span {
+      name: attributes.metadata().name(),
+      fields: HashMap::new(),
+    };
+    attributes.record(&mut span);
+
+    self.spans.lock().unwrap().insert(id, span);
+    Id::from_u64(id)
+  }
+
+  fn record(&self, id: &Id, values: &Record) {
+    if let Some(span) = self.spans.lock().unwrap().get_mut(&id.into_u64()) {
+      values.record(span);
+    }
+  }
+
+  fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+  fn event(&self, _: &Event) {}
+
+  fn enter(&self, _: &Id) {}
+
+  fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn tracing() {
+  let env = common::java_env("Embedded");
+  let spans = Spans::default();
+
+  tracing::subscriber::with_default(spans.clone(), || {
+    let class = env.get_class("Embedded").expect("Failed to get class");
+
+    class
+      .call_static_method(
+        "add",
+        Signature::new(&[Type::Int, Type::Int], Type::Int),
+        &[Value::Int(2), Value::Int(3)],
+      )
+      .expect("Failed to call add");
+    assert!(class
+      .call_static_method("missing", Signature::new(&[], Type::Void), &[])
+      .is_err());
+
+    let array = env.new_int_array(1);
+    array.set(0, 5).expect("Failed to set element");
+    assert_eq!(array.get(0).expect("Failed to get element"), 5);
+
+    let string = env.new_string("traced").expect("Failed to create string");
+    Object::new(&env, &string)
+      .call_method("length", Signature::new(&[], Type::Int), &[])
+      .expect("Failed to call length");
+
+    // The lookups fail before the operations are finished
+    let object = Object::new(&env, &string);
+    assert!(object
+      .invoke_private("missing", Signature::new(&[], Type::Int), &[])
+      .is_err());
+    assert!(object.get_field_dynamic("missing").is_err());
+    env
+      .get_string_chars((&string).into())
+      .expect("Failed to get chars");
+
+    let strings = env.new_object_array(1, "java/lang/String");
+    let element = env.new_string("element").expect("Failed to create string");
+    strings.set(0, element).expect("Failed to set element");
+    strings.get(0).expect("Failed to get element");
+  });
+
+  let find_class = spans.named("find_class");
+  assert_eq!(find_class.len(), 1);
+  assert_eq!(find_class[0]["class"], "Embedded");
+  assert_eq!(find_class[0]["error"], "false");
+
+  let calls = spans.named("call_static_method");
+  assert_eq!(calls.len(), 2);
+  assert_eq!(calls[0]["class"], "Embedded");
+  assert_eq!(calls[0]["member"], "add");
+  assert_eq!(calls[0]["descriptor"], "(II)I");
+  assert_eq!(calls[0]["error"], "false");
+  assert!(calls[0]["duration_us"].parse::<u64>().is_ok());
+  assert_eq!(calls[1]["member"], "missing");
+  assert_eq!(calls[1]["error"], "true");
+
+  for name in ["set_array_region", "get_array_region"] {
+    let regions = spans.named(name);
+    assert_eq!(regions.len(), 1, "{}", name);
+    assert_eq!(regions[0]["class"], "[I");
+  }
+
+  let strings = spans.named("new_string");
+  assert_eq!(strings.len(), 2);
+  assert_eq!(strings[0]["class"], "java.lang.String");
+
+  let methods = spans.named("call_method");
+  assert_eq!(methods.len(), 1);
+  assert_eq!(methods[0]["class"], "java.lang.String");
+  assert_eq!(methods[0]["member"], "length");
+  assert_eq!(methods[0]["descriptor"], "()I");
+
+  for name in ["invoke_private", "get_field_dynamic"] {
+    let reflected = spans.named(name);
+    assert_eq!(reflected.len(), 1, "{}", name);
+    assert_eq!(reflected[0]["class"], "java.lang.String");
+    assert_eq!(reflected[0]["member"], "missing");
+    assert_eq!(reflected[0]["error"], "true");
+    assert!(reflected[0]["duration_us"].parse::<u64>().is_ok());
+  }
+
+  assert_eq!(spans.named("get_string_chars").len(), 1);
+
+  for name in ["set_array_element", "get_array_element"] {
+    let elements = spans.named(name);
+    assert_eq!(elements.len(), 1, "{}", name);
+    assert_eq!(elements[0]["class"], "[Ljava.lang.String;");
+  }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use syn::{Attribute, LitStr};
+
+/// Parses every `#[jnat(key = "value")]` attribute into a map of keys to values
+///
+/// # Arguments
+///
+/// * `attrs` - The attributes to parse
+/// * `allowed` - The keys that may be used
+pub fn parse_attributes(
+  attrs: &[Attribute],
+  allowed: &[&str],
+) -> syn::Result<HashMap<String, LitStr>> {
+  let mut values = HashMap::new();
+
+  for attr in attrs.iter().filter(|a| a.path().is_ident("jnat")) {
+    attr.parse_nested_meta(|meta| {
+      let key = meta
+        .path
+        .get_ident()
+        .map(|i| i.to_string())
+        .unwrap_or_default();
+
+      if !allowed.contains(&key.as_str()) {
+        return Err(meta.error(format!(
+          "unknown jnat attribute, expected one of: {}",
+          allowed.join(", ")
+        )));
+      }
+
+      values.insert(key, meta.value()?.parse()?);
+      Ok(())
+    })?;
+  }
+
+  Ok(values)
+}
+
+/// Converts a Rust identifier to the SCREAMING_SNAKE_CASE used for Java constants
+///
+/// # Arguments
+///
+/// * `ident` - The identifier to convert
+pub fn screaming_snake_case(ident: &str) -> String {
+  let chars = ident.chars().collect::<Vec<char>>();
+  let mut result = String::new();
+
+  for (i, c) in chars.iter().enumerate() {
+    if i > 0 && c.is_uppercase() {
+      let previous = chars[i - 1];
+      let next = chars.get(i + 1);
+
+      if previous.is_lowercase()
+        || previous.is_ascii_digit()
+        || (previous.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+      {
+        result.push('_');
+      }
+    }
+
+    if *c != '_' || !result.ends_with('_') {
+      result.extend(c.to_uppercase());
+    }
+  }
+
+  result
+}
@@ -0,0 +1,178 @@
+use crate::result_ok_type;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+  meta::ParseNestedMeta, spanned::Spanned, FnArg, Ident, ItemFn, LitStr, ReturnType, Type,
+};
+
+/// The JNI versions that `#[on_load(version = "...")]` accepts, and their `jnat::Version` variants
+const VERSIONS: &[(&str, &str)] = &[
+  ("1.1", "V1_1"),
+  ("1.2", "V1_2"),
+  ("1.4", "V1_4"),
+  ("1.6", "V1_6"),
+  ("1.8", "V1_8"),
+  ("9", "V9"),
+  ("10", "V10"),
+  ("19", "V19"),
+  ("20", "V20"),
+  ("21", "V21"),
+  ("24", "V24"),
+];
+
+/// The arguments of `#[on_load]`
+#[derive(Default)]
+pub struct OnLoadArgs {
+  version: Option<LitStr>,
+}
+
+impl OnLoadArgs {
+  /// Parses a `version = "..."` argument
+  pub fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+    if meta.path.is_ident("version") {
+      self.version = Some(meta.value()?.parse()?);
+      Ok(())
+    } else {
+      Err(meta.error("unknown on_load argument, expected: version"))
+    }
+  }
+}
+
+/// Checks that a hook takes a single `Env` argument
+///
+/// The raw arguments of `JNI_OnLoad` and `JNI_OnUnload` (`*mut JavaVM` and `*mut c_void`) are
+/// rejected, as the generated function takes them.
+fn check_inputs(function: &ItemFn, hook: &str) -> syn::Result<()> {
+  let error = |span| {
+    syn::Error::new(
+      span,
+      format!(
+        "{} functions take a single jnat::Env, not the *mut JavaVM and *mut c_void of JNI",
+        hook
+      ),
+    )
+  };
+
+  let mut inputs = function.sig.inputs.iter();
+  match (inputs.next(), inputs.next()) {
+    (Some(FnArg::Typed(input)), None) if is_env(&input.ty) => Ok(()),
+    (Some(input), None) => Err(error(input.span())),
+    _ => Err(error(function.sig.inputs.span())),
+  }
+}
+
+/// Checks whether a type is `Env`, e.g. `jnat::Env` or `Env<'_>`
+fn is_env(ty: &Type) -> bool {
+  let Type::Path(path) = ty else {
+    return false;
+  };
+
+  path.qself.is_none() && path.path.segments.last().is_some_and(|s| s.ident == "Env")
+}
+
+pub fn on_load(args: OnLoadArgs, function: ItemFn) -> syn::Result<TokenStream> {
+  check_inputs(&function, "on_load")?;
+
+  let version = match &args.version {
+    Some(version) => {
+      let (_, variant) = VERSIONS
+        .iter()
+        .find(|(name, _)| *name == version.value())
+        .ok_or_else(|| {
+          syn::Error::new(
+            version.span(),
+            format!(
+              "unknown JNI version, expected one of: {}",
+              VERSIONS
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<&str>>()
+                .join(", ")
+            ),
+          )
+        })?;
+
+      Ident::new(variant, version.span())
+    }
+    None => Ident::new("V1_8", function.sig.ident.span()),
+  };
+
+  let name = &function.sig.ident;
+  let is_result = match &function.sig.output {
+    ReturnType::Type(_, ty) => result_ok_type(ty)?.is_some(),
+    ReturnType::Default => false,
+  };
+
+  let result = if is_result {
+    quote! {
+      match value {
+        ::std::result::Result::Ok(_) => version.to_raw(),
+        ::std::result::Result::Err(error) => {
+          // An exception left pending, e.g. one that could not be taken, reaches Java as is. If
+          // throwing fails, an exception describing why is already pending.
+          if !env.get_jni_env().exception_check().unwrap_or(true) {
+            let _ = ::jnat::JavaThrowable::throw(&error, &env);
+          }
+          ::jnat::__private::jni::sys::JNI_ERR
+        }
+      }
+    }
+  } else {
+    quote! { version.to_raw() }
+  };
+
+  // An exception pending when JNI_OnLoad returns is thrown by System.loadLibrary
+  Ok(quote! {
+    #function
+
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub unsafe extern "system" fn JNI_OnLoad(
+      vm: *mut ::jnat::__private::jni::sys::JavaVM,
+      _: *mut ::std::ffi::c_void,
+    ) -> ::jnat::__private::jni::sys::jint {
+      let _frame = ::jnat::__private::NativeFrame::enter();
+      let (jni_env, version) = match unsafe { ::jnat::load_java_vm(vm, ::jnat::Version::#version) } {
+        ::std::result::Result::Ok(loaded) => loaded,
+        ::std::result::Result::Err(code) => return code,
+      };
+      let env = ::jnat::Env::new(&jni_env);
+
+      match ::jnat::__private::catch_unwind(|| #name(env)) {
+        ::std::result::Result::Ok(value) => #result,
+        ::std::result::Result::Err(payload) => {
+          ::jnat::throw_panic(&env, &*payload, "java/lang/RuntimeException");
+          ::jnat::__private::jni::sys::JNI_ERR
+        }
+      }
+    }
+  })
+}
+
+pub fn on_unload(function: ItemFn) -> syn::Result<TokenStream> {
+  check_inputs(&function, "on_unload")?;
+
+  let name = &function.sig.ident;
+
+  // Nothing can catch an exception while the library is unloaded, so errors and panics are dropped
+  Ok(quote! {
+    #function
+
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub unsafe extern "system" fn JNI_OnUnload(
+      vm: *mut ::jnat::__private::jni::sys::JavaVM,
+      _: *mut ::std::ffi::c_void,
+    ) {
+      let _frame = ::jnat::__private::NativeFrame::enter();
+      let ::std::option::Option::Some(jni_env) = (unsafe { ::jnat::unload_java_vm(vm) }) else {
+        return;
+      };
+      let env = ::jnat::Env::new(&jni_env);
+
+      let _ = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+        let _ = #name(env);
+      }));
+    }
+  })
+}
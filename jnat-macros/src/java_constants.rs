@@ -0,0 +1,93 @@
+use crate::attributes::{parse_attributes, screaming_snake_case};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Data, DeriveInput, Fields};
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+  let fields = match &input.data {
+    Data::Struct(data) => match &data.fields {
+      Fields::Named(fields) => &fields.named,
+      _ => {
+        return Err(syn::Error::new(
+          data.fields.span(),
+          "JavaConstants requires named fields",
+        ))
+      }
+    },
+    _ => {
+      return Err(syn::Error::new(
+        input.span(),
+        "JavaConstants can only be derived for structs",
+      ))
+    }
+  };
+
+  if !input.generics.params.is_empty() {
+    return Err(syn::Error::new(
+      input.generics.span(),
+      "JavaConstants cannot be derived for generic structs",
+    ));
+  }
+
+  let class = parse_attributes(&input.attrs, &["class"])?
+    .remove("class")
+    .ok_or_else(|| {
+      syn::Error::new(
+        input.ident.span(),
+        "JavaConstants requires the Java class, e.g. #[jnat(class = \"com/example/Protocol\")]",
+      )
+    })?;
+
+  let mut idents = Vec::new();
+  let mut types = Vec::new();
+  let mut names = Vec::new();
+
+  for field in fields {
+    let ident = field.ident.as_ref().expect("named fields have identifiers");
+
+    let (name, span) = match parse_attributes(&field.attrs, &["name"])?.remove("name") {
+      Some(name) => (name.value(), name.span()),
+      None => (screaming_snake_case(&ident.to_string()), ident.span()),
+    };
+
+    if names.contains(&name) {
+      return Err(syn::Error::new(
+        span,
+        format!("the constant {} is already mapped to another field", name),
+      ));
+    }
+
+    idents.push(ident);
+    types.push(&field.ty);
+    names.push(name);
+  }
+
+  let ident = &input.ident;
+
+  Ok(quote! {
+    impl ::jnat::JavaConstants for #ident {
+      const CLASS: &'static str = #class;
+
+      const CONSTANTS: &'static [(&'static str, ::jnat::Type<'static>)] = &[
+        #((#names, <#types as ::jnat::FromConstant>::TYPE),)*
+      ];
+
+      fn from_constants<'a>(
+        env: &'a ::jnat::Env<'a>,
+        mut constants: ::std::collections::HashMap<::std::string::String, ::jnat::ReturnValue<'a>>,
+      ) -> ::jnat::Result<Self> {
+        ::std::result::Result::Ok(Self {
+          #(#idents: ::jnat::FromConstant::from_constant(
+            env,
+            constants.remove(#names).ok_or_else(|| {
+              ::jnat::__private::jni::errors::Error::FieldNotFound {
+                name: #names.into(),
+                sig: <#types as ::jnat::FromConstant>::TYPE.into(),
+              }
+            })?,
+          )?,)*
+        })
+      }
+    }
+  })
+}
@@ -0,0 +1,64 @@
+use crate::attributes::{parse_attributes, screaming_snake_case};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Data, DeriveInput, Fields};
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+  let Data::Enum(data) = &input.data else {
+    return Err(syn::Error::new(
+      input.span(),
+      "JavaEnum can only be derived for enums",
+    ));
+  };
+
+  let class = parse_attributes(&input.attrs, &["class"])?
+    .remove("class")
+    .ok_or_else(|| {
+      syn::Error::new(
+        input.ident.span(),
+        "JavaEnum requires the Java class, e.g. #[jnat(class = \"com/example/Color\")]",
+      )
+    })?;
+
+  let mut variants = Vec::new();
+  let mut names = Vec::new();
+
+  for variant in &data.variants {
+    if !matches!(variant.fields, Fields::Unit) {
+      return Err(syn::Error::new(
+        variant.fields.span(),
+        "JavaEnum variants cannot have fields",
+      ));
+    }
+
+    let name = match parse_attributes(&variant.attrs, &["name"])?.remove("name") {
+      Some(name) => name.value(),
+      None => screaming_snake_case(&variant.ident.to_string()),
+    };
+
+    variants.push(&variant.ident);
+    names.push(name);
+  }
+
+  let ident = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  Ok(quote! {
+    impl #impl_generics ::jnat::JavaEnum for #ident #ty_generics #where_clause {
+      const CLASS: &'static str = #class;
+
+      fn from_name(name: &str) -> ::std::option::Option<Self> {
+        match name {
+          #(#names => ::std::option::Option::Some(Self::#variants),)*
+          _ => ::std::option::Option::None,
+        }
+      }
+
+      fn name(&self) -> &'static str {
+        match self {
+          #(Self::#variants => #names,)*
+        }
+      }
+    }
+  })
+}
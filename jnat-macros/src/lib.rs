@@ -1,14 +1,20 @@
+mod attributes;
+mod hooks;
+mod java_constants;
+mod java_enum;
+
 use proc_macro::TokenStream;
 use quote::ToTokens;
 use syn::{
-  parse::Parse, parse_macro_input, punctuated::Punctuated, spanned::Spanned, Ident, Path, Token,
-  Type,
+  parse::Parse, parse_macro_input, punctuated::Punctuated, spanned::Spanned, DeriveInput, Ident,
+  ItemFn, LitStr, Path, Token, Type,
 };
 
 struct JavaNativeMethod {
   qualified_class_name: Path,
   method_name: Ident,
   signature: MethodSignature,
+  panic_class: Option<LitStr>,
 }
 
 struct MethodSignature {
@@ -23,10 +29,22 @@ impl Parse for JavaNativeMethod {
     let method_name = input.parse()?;
     input.parse::<Token![,]>()?;
     let signature = input.parse()?;
+
+    let mut panic_class = None;
+    if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+      let key = input.parse::<Ident>()?;
+      if key != "panic" {
+        return Err(syn::Error::new(key.span(), "expected `panic = \"java/lang/Class\"`"));
+      }
+      input.parse::<Token![=]>()?;
+      panic_class = Some(input.parse()?);
+    }
+
     Ok(JavaNativeMethod {
       qualified_class_name,
       method_name,
       signature,
+      panic_class,
     })
   }
 }
@@ -50,6 +68,39 @@ struct ParamWithName {
   ty: Type,
 }
 
+/// Gets `T` if a type is written as `Result<T, E>` or `jnat::Result<T>`
+///
+/// Returns an error for other types named `Result`, such as `io::Result<T>`, as their error may not
+/// implement `jnat::JavaThrowable`
+fn result_ok_type(ty: &Type) -> syn::Result<Option<&Type>> {
+  let Type::Path(path) = ty else {
+    return Ok(None);
+  };
+  let Some(segment) = path.path.segments.last() else {
+    return Ok(None);
+  };
+  if segment.ident != "Result" {
+    return Ok(None);
+  }
+
+  let prefix = path.path.segments.iter().rev().skip(1).map(|s| s.ident.to_string()).collect::<Vec<String>>();
+  let prefix = prefix.iter().rev().map(String::as_str).collect::<Vec<&str>>();
+  let arguments = match &segment.arguments {
+    syn::PathArguments::AngleBracketed(arguments) if path.qself.is_none() => arguments.args.iter().collect::<Vec<_>>(),
+    _ => Vec::new(),
+  };
+
+  let ok_type = match (prefix.as_slice(), arguments.as_slice()) {
+    ([] | ["std", "result"] | ["core", "result"], [syn::GenericArgument::Type(ty), syn::GenericArgument::Type(_)]) => Some(ty),
+    (["jnat"], [syn::GenericArgument::Type(ty)]) => Some(ty),
+    _ => None,
+  };
+
+  ok_type.map(Some).ok_or_else(|| {
+    syn::Error::new(ty.span(), "expected jnat::Result<T> or Result<T, E>, where E implements jnat::JavaThrowable")
+  })
+}
+
 impl ToTokens for ParamWithName {
   fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
     let name = &self.name;
@@ -66,13 +117,28 @@ pub fn jnat(input: TokenStream) -> TokenStream {
 
   #[no_mangle]
   #[allow(non_snake_case)]
-  pub extern "system" fn Java_com_example_jnat_HelloWorld_hello(env: jni::JNIEnv, class: jni::objects::JClass) -> jni::sys::jint {
-    hello(env, class)
+  pub extern "system" fn Java_com_example_jnat_HelloWorld_hello(arg0: jni::JNIEnv, arg1: jni::objects::JClass) -> jni::sys::jint {
+    let env = unsafe { arg0.unsafe_clone() };
+    match jnat::__private::catch_unwind(|| hello(arg0, arg1)) {
+      Ok(value) => value,
+      Err(payload) => {
+        jnat::throw_panic(&jnat::Env::new(&env), &*payload, "java/lang/RuntimeException");
+        jnat::NullValue::null_value()
+      }
+    }
   }
 
   On the input:
 
-  jnat!(hello)
+  jnat!(com.example.jnat.HelloWorld, hello, (JNIEnv, JClass) -> jint)
+
+  Panics are thrown as a java/lang/RuntimeException, or as the class given with
+  jnat!(..., panic = "java/lang/IllegalStateException"). Their Rust frames are added to the stack
+  trace once jnat::install_panic_hook has been called.
+
+  A return type of Result<T, E>, where E implements jnat::JavaThrowable, or jnat::Result<T>
+  makes the function return T, and throws the error with JavaThrowable::throw. Other types named
+  Result, such as io::Result<T>, are rejected.
 
   fn hello(env: jni::JNIEnv, class: jni::objects::JClass) -> jni::sys::jint {
     42
@@ -84,29 +150,128 @@ pub fn jnat(input: TokenStream) -> TokenStream {
     qualified_class_name,
     method_name,
     signature,
+    panic_class,
   } = parse_macro_input!(input as JavaNativeMethod);
 
+  if signature.params.is_empty() {
+    return syn::Error::new(method_name.span(), "native methods take at least a JNIEnv")
+      .into_compile_error()
+      .into();
+  }
+
+  let panic_class = panic_class.map(|c| c.value()).unwrap_or("java/lang/RuntimeException".into());
+
   let method_name_str = &*method_name.to_string();
 
   let full_method_name = qualified_class_name.segments.iter().map(|s| s.ident.to_string()).collect::<Vec<String>>().join("_");
   let full_method_name = format!("Java_{}_{}", full_method_name, method_name_str);
   let full_method_name = Ident::new(&full_method_name, qualified_class_name.span());
 
-  let return_type = signature.return_type;
+  let ok_type = match result_ok_type(&signature.return_type) {
+    Ok(ok_type) => ok_type,
+    Err(error) => return error.into_compile_error().into(),
+  };
+
+  let (return_type, value) = match ok_type {
+    Some(ok_type) => (ok_type.clone(), quote::quote! {
+      match value {
+        ::std::result::Result::Ok(value) => value,
+        ::std::result::Result::Err(error) => {
+          let env = ::jnat::Env::new(&env);
+
+          // An exception left pending, e.g. one that could not be taken, reaches Java as is. If
+          // throwing fails, an exception describing why is already pending.
+          if !env.get_jni_env().exception_check().unwrap_or(true) {
+            let _ = ::jnat::JavaThrowable::throw(&error, &env);
+          }
+          ::jnat::NullValue::null_value()
+        }
+      }
+    }),
+    None => (signature.return_type, quote::quote! { value }),
+  };
   let params = signature.params.iter().enumerate().map(|(i, ty)| ParamWithName {
     name: Ident::new(&format!("arg{}", i), ty.span()),
     ty: ty.clone(),
   }).collect::<Vec<ParamWithName>>();
 
   let param_names = params.iter().map(|p| &p.name).collect::<Vec<&Ident>>();
+  let env_name = param_names[0];
 
+  // Unwinding across the FFI boundary is undefined behavior, so panics are thrown into Java
   let output = quote::quote! {
     #[no_mangle]
     #[allow(non_snake_case)]
     pub extern "system" fn #full_method_name(#(#params),*) -> #return_type {
-      #method_name(#(#param_names),*)
+      // The JVM frees the local references of the call when it returns
+      let _frame = ::jnat::__private::NativeFrame::enter();
+      let env = unsafe { #env_name.unsafe_clone() };
+
+      match ::jnat::__private::catch_unwind(|| #method_name(#(#param_names),*)) {
+        ::std::result::Result::Ok(value) => #value,
+        ::std::result::Result::Err(payload) => {
+          ::jnat::throw_panic(&::jnat::Env::new(&env), &*payload, #panic_class);
+          ::jnat::NullValue::null_value()
+        }
+      }
     }
   };
 
   output.into()
 }
+
+/// Implements `jnat::JavaEnum` for a fieldless enum
+///
+/// The Java class is given with `#[jnat(class = "com/example/Color")]`. Variants map to the
+/// SCREAMING_SNAKE_CASE version of their name, which can be overridden with
+/// `#[jnat(name = "NAME")]`.
+#[proc_macro_derive(JavaEnum, attributes(jnat))]
+pub fn java_enum(input: TokenStream) -> TokenStream {
+  java_enum::derive(parse_macro_input!(input as DeriveInput))
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
+
+/// Implements `jnat::JavaConstants` for a struct with named fields
+///
+/// The Java class is given with `#[jnat(class = "com/example/Protocol")]`. Fields map to the
+/// SCREAMING_SNAKE_CASE version of their name, which can be overridden with
+/// `#[jnat(name = "NAME")]`. Field types must implement `jnat::FromConstant`.
+#[proc_macro_derive(JavaConstants, attributes(jnat))]
+pub fn java_constants(input: TokenStream) -> TokenStream {
+  java_constants::derive(parse_macro_input!(input as DeriveInput))
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
+
+/// Generates `JNI_OnLoad`, calling the function when the library is loaded
+///
+/// The function takes a `jnat::Env` and returns `()` or a `Result` whose error implements
+/// `jnat::JavaThrowable`, which is thrown by `System.loadLibrary`. The JVM is stored for
+/// `jnat::Env::current` before the function is called. The JNI version the library needs is given
+/// with `#[on_load(version = "21")]`, 1.8 by default, and loading fails on older JVMs. Newer JVMs
+/// get the newest version both jnat and the JVM support.
+#[proc_macro_attribute]
+pub fn on_load(args: TokenStream, input: TokenStream) -> TokenStream {
+  let mut on_load_args = hooks::OnLoadArgs::default();
+  let parser = syn::meta::parser(|meta| on_load_args.parse(meta));
+  parse_macro_input!(args with parser);
+
+  hooks::on_load(on_load_args, parse_macro_input!(input as ItemFn))
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
+
+/// Generates `JNI_OnUnload`, calling the function when the class loader of the library is
+/// garbage collected
+///
+/// The function takes a `jnat::Env`. Its result is ignored and panics are dropped, as nothing can
+/// catch them.
+#[proc_macro_attribute]
+pub fn on_unload(args: TokenStream, input: TokenStream) -> TokenStream {
+  parse_macro_input!(args as syn::parse::Nothing);
+
+  hooks::on_unload(parse_macro_input!(input as ItemFn))
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
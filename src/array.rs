@@ -1,21 +1,38 @@
-use crate::Env;
-use jni::{
-  errors::Result,
-  objects::{
-    JBooleanArray, JByteArray, JCharArray, JDoubleArray, JFloatArray, JIntArray, JLongArray,
-    JObject, JObjectArray, JShortArray,
-  },
+use crate::{local_ref, trace::operation, Env, Error};
+use jni::objects::{
+  JBooleanArray, JByteArray, JCharArray, JDoubleArray, JFloatArray, JIntArray, JLongArray, JObject,
+  JObjectArray, JShortArray,
 };
 
 /// A trait for wrapped JNI arrays
-pub trait Array<'a, T, J> {
+pub trait Array<'a, T, J>: Sized {
   /// Create a new array with the given length
   ///
+  /// Returns `Error::Exception` if the JVM throws, e.g. an `OutOfMemoryError`
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  fn try_new(env: &'a Env<'a>, length: usize) -> crate::Result<Self>;
+
+  /// Create a new array with the given length
+  ///
+  /// This is a convenience for `try_new`
+  ///
+  /// # Panics
+  ///
+  /// Panics if the array cannot be created
+  ///
   /// # Arguments
   ///
   /// * `env` - The environment
   /// * `length` - The length of the array
-  fn new(env: &'a Env<'a>, length: usize) -> Self;
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  fn new(env: &'a Env<'a>, length: usize) -> Self {
+    Self::try_new(env, length).expect("Failed to create array")
+  }
 
   /// Create a new array from a JNI array
   ///
@@ -25,23 +42,73 @@ pub trait Array<'a, T, J> {
   /// * `array` - The JNI array
   fn from(env: &'a Env<'a>, array: J) -> Self;
 
+  /// Get the wrapped JNI array, e.g. to delete it with `Env::delete_local_ref`
+  fn get_array(self) -> J;
+
+  /// Get the length of the array
+  fn try_length(&self) -> crate::Result<usize>;
+
   /// Get the length of the array
-  fn length(&self) -> usize;
+  ///
+  /// This is a convenience for `try_length`
+  ///
+  /// # Panics
+  ///
+  /// Panics if the length cannot be read, e.g. because the wrapped array is null
+  fn length(&self) -> usize {
+    self.try_length().expect("Failed to get array length")
+  }
 
   /// Get the value at the given index
   ///
+  /// Returns `Error::IndexOutOfBounds` if the index is not in the array
+  ///
   /// # Arguments
   ///
   /// * `index` - The index
-  fn get(&self, index: usize) -> Result<T>;
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  fn get(&self, index: usize) -> crate::Result<T>;
 
   /// Set the value at the given index
   ///
+  /// Returns `Error::IndexOutOfBounds` if the index is not in the array
+  ///
   /// # Arguments
   ///
   /// * `index` - The index
   /// * `value` - The value
-  fn set(&self, index: usize, value: T) -> Result<()>;
+  fn set(&self, index: usize, value: T) -> crate::Result<()>;
+}
+
+/// Checks an index against the length of an array, and converts it into a JNI index
+///
+/// The JVM would otherwise throw an `ArrayIndexOutOfBoundsException`, and indices above
+/// `i32::MAX` would wrap
+///
+/// # Arguments
+///
+/// * `array` - The array
+/// * `index` - The index
+fn checked_index<'a, T, J>(array: &impl Array<'a, T, J>, index: usize) -> crate::Result<i32> {
+  let length = array.try_length()?;
+
+  if index >= length {
+    return Err(Error::IndexOutOfBounds { index, length });
+  }
+
+  // Array lengths are at most i32::MAX, so the index fits
+  Ok(index as i32)
+}
+
+/// Converts a length or index into a JNI size, which is at most `i32::MAX`
+///
+/// Returns `Error::LengthTooLarge` if it does not fit, as it would wrap otherwise
+///
+/// # Arguments
+///
+/// * `length` - The length or index
+pub(crate) fn checked_length(length: usize) -> crate::Result<i32> {
+  i32::try_from(length).map_err(|_| Error::LengthTooLarge { length })
 }
 
 /// A struct wrapping a JNI boolean array
@@ -51,38 +118,53 @@ pub struct BooleanArray<'a> {
 }
 
 impl<'a> Array<'a, bool, JBooleanArray<'a>> for BooleanArray<'a> {
-  fn new(env: &'a Env<'a>, length: usize) -> Self {
+  fn try_new(env: &'a Env<'a>, length: usize) -> crate::Result<Self> {
     let jni_env = env.get_jni_env();
-    let array = jni_env.new_boolean_array(length as i32).unwrap();
+    let array = env.catch(jni_env.new_boolean_array(checked_length(length)?))?;
+    local_ref::track(&array, "Array::try_new");
 
-    BooleanArray { env, array }
+    Ok(BooleanArray { env, array })
   }
 
   fn from(env: &'a Env<'a>, array: JBooleanArray<'a>) -> Self {
     BooleanArray { env, array }
   }
 
-  fn length(&self) -> usize {
+  fn get_array(self) -> JBooleanArray<'a> {
+    self.array
+  }
+
+  fn try_length(&self) -> crate::Result<usize> {
     let jni_env = self.env.get_jni_env();
-    jni_env.get_array_length(&self.array).unwrap() as usize
+    Ok(jni_env.get_array_length(&self.array)? as usize)
   }
 
-  fn get(&self, index: usize) -> Result<bool> {
+  fn get(&self, index: usize) -> crate::Result<bool> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let mut buf: [u8; 1] = [0];
-    jni_env.get_boolean_array_region(&self.array, index, &mut buf)?;
+    let operation = operation!("get_array_region", class = Some(String::from("[Z")));
+    let result = self
+      .env
+      .catch(jni_env.get_boolean_array_region(&self.array, index, &mut buf));
+    operation.finish(&result);
+    result?;
 
     Ok(buf[0] != 0)
   }
 
-  fn set(&self, index: usize, value: bool) -> Result<()> {
+  fn set(&self, index: usize, value: bool) -> crate::Result<()> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let buf: [u8; 1] = [value as u8];
-    jni_env.set_boolean_array_region(&self.array, index, &buf)?;
+    let operation = operation!("set_array_region", class = Some(String::from("[Z")));
+    let result = self
+      .env
+      .catch(jni_env.set_boolean_array_region(&self.array, index, &buf));
+    operation.finish(&result);
+    result?;
 
     Ok(())
   }
@@ -95,38 +177,53 @@ pub struct ByteArray<'a> {
 }
 
 impl<'a> Array<'a, i8, JByteArray<'a>> for ByteArray<'a> {
-  fn new(env: &'a Env<'a>, length: usize) -> Self {
+  fn try_new(env: &'a Env<'a>, length: usize) -> crate::Result<Self> {
     let jni_env = env.get_jni_env();
-    let array = jni_env.new_byte_array(length as i32).unwrap();
+    let array = env.catch(jni_env.new_byte_array(checked_length(length)?))?;
+    local_ref::track(&array, "Array::try_new");
 
-    ByteArray { env, array }
+    Ok(ByteArray { env, array })
   }
 
   fn from(env: &'a Env<'a>, array: JByteArray<'a>) -> Self {
     ByteArray { env, array }
   }
 
-  fn length(&self) -> usize {
+  fn get_array(self) -> JByteArray<'a> {
+    self.array
+  }
+
+  fn try_length(&self) -> crate::Result<usize> {
     let jni_env = self.env.get_jni_env();
-    jni_env.get_array_length(&self.array).unwrap() as usize
+    Ok(jni_env.get_array_length(&self.array)? as usize)
   }
 
-  fn get(&self, index: usize) -> Result<i8> {
+  fn get(&self, index: usize) -> crate::Result<i8> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let mut buf: [i8; 1] = [0];
-    jni_env.get_byte_array_region(&self.array, index, &mut buf)?;
+    let operation = operation!("get_array_region", class = Some(String::from("[B")));
+    let result = self
+      .env
+      .catch(jni_env.get_byte_array_region(&self.array, index, &mut buf));
+    operation.finish(&result);
+    result?;
 
     Ok(buf[0])
   }
 
-  fn set(&self, index: usize, value: i8) -> Result<()> {
+  fn set(&self, index: usize, value: i8) -> crate::Result<()> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let buf: [i8; 1] = [value];
-    jni_env.set_byte_array_region(&self.array, index, &buf)?;
+    let operation = operation!("set_array_region", class = Some(String::from("[B")));
+    let result = self
+      .env
+      .catch(jni_env.set_byte_array_region(&self.array, index, &buf));
+    operation.finish(&result);
+    result?;
 
     Ok(())
   }
@@ -139,38 +236,53 @@ pub struct CharArray<'a> {
 }
 
 impl<'a> Array<'a, char, JCharArray<'a>> for CharArray<'a> {
-  fn new(env: &'a Env<'a>, length: usize) -> Self {
+  fn try_new(env: &'a Env<'a>, length: usize) -> crate::Result<Self> {
     let jni_env = env.get_jni_env();
-    let array = jni_env.new_char_array(length as i32).unwrap();
+    let array = env.catch(jni_env.new_char_array(checked_length(length)?))?;
+    local_ref::track(&array, "Array::try_new");
 
-    CharArray { env, array }
+    Ok(CharArray { env, array })
   }
 
   fn from(env: &'a Env<'a>, array: JCharArray<'a>) -> Self {
     CharArray { env, array }
   }
 
-  fn length(&self) -> usize {
+  fn get_array(self) -> JCharArray<'a> {
+    self.array
+  }
+
+  fn try_length(&self) -> crate::Result<usize> {
     let jni_env = self.env.get_jni_env();
-    jni_env.get_array_length(&self.array).unwrap() as usize
+    Ok(jni_env.get_array_length(&self.array)? as usize)
   }
 
-  fn get(&self, index: usize) -> Result<char> {
+  fn get(&self, index: usize) -> crate::Result<char> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let mut buf: [u16; 1] = [0];
-    jni_env.get_char_array_region(&self.array, index, &mut buf)?;
+    let operation = operation!("get_array_region", class = Some(String::from("[C")));
+    let result = self
+      .env
+      .catch(jni_env.get_char_array_region(&self.array, index, &mut buf));
+    operation.finish(&result);
+    result?;
 
     Ok(buf[0] as u8 as char)
   }
 
-  fn set(&self, index: usize, value: char) -> Result<()> {
+  fn set(&self, index: usize, value: char) -> crate::Result<()> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let buf: [u16; 1] = [value as u16];
-    jni_env.set_char_array_region(&self.array, index, &buf)?;
+    let operation = operation!("set_array_region", class = Some(String::from("[C")));
+    let result = self
+      .env
+      .catch(jni_env.set_char_array_region(&self.array, index, &buf));
+    operation.finish(&result);
+    result?;
 
     Ok(())
   }
@@ -183,38 +295,53 @@ pub struct DoubleArray<'a> {
 }
 
 impl<'a> Array<'a, f64, JDoubleArray<'a>> for DoubleArray<'a> {
-  fn new(env: &'a Env<'a>, length: usize) -> Self {
+  fn try_new(env: &'a Env<'a>, length: usize) -> crate::Result<Self> {
     let jni_env = env.get_jni_env();
-    let array = jni_env.new_double_array(length as i32).unwrap();
+    let array = env.catch(jni_env.new_double_array(checked_length(length)?))?;
+    local_ref::track(&array, "Array::try_new");
 
-    DoubleArray { env, array }
+    Ok(DoubleArray { env, array })
   }
 
   fn from(env: &'a Env<'a>, array: JDoubleArray<'a>) -> Self {
     DoubleArray { env, array }
   }
 
-  fn length(&self) -> usize {
+  fn get_array(self) -> JDoubleArray<'a> {
+    self.array
+  }
+
+  fn try_length(&self) -> crate::Result<usize> {
     let jni_env = self.env.get_jni_env();
-    jni_env.get_array_length(&self.array).unwrap() as usize
+    Ok(jni_env.get_array_length(&self.array)? as usize)
   }
 
-  fn get(&self, index: usize) -> Result<f64> {
+  fn get(&self, index: usize) -> crate::Result<f64> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let mut buf: [f64; 1] = [0.0];
-    jni_env.get_double_array_region(&self.array, index, &mut buf)?;
+    let operation = operation!("get_array_region", class = Some(String::from("[D")));
+    let result = self
+      .env
+      .catch(jni_env.get_double_array_region(&self.array, index, &mut buf));
+    operation.finish(&result);
+    result?;
 
     Ok(buf[0])
   }
 
-  fn set(&self, index: usize, value: f64) -> Result<()> {
+  fn set(&self, index: usize, value: f64) -> crate::Result<()> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let buf: [f64; 1] = [value];
-    jni_env.set_double_array_region(&self.array, index, &buf)?;
+    let operation = operation!("set_array_region", class = Some(String::from("[D")));
+    let result = self
+      .env
+      .catch(jni_env.set_double_array_region(&self.array, index, &buf));
+    operation.finish(&result);
+    result?;
 
     Ok(())
   }
@@ -227,38 +354,53 @@ pub struct FloatArray<'a> {
 }
 
 impl<'a> Array<'a, f32, JFloatArray<'a>> for FloatArray<'a> {
-  fn new(env: &'a Env<'a>, length: usize) -> Self {
+  fn try_new(env: &'a Env<'a>, length: usize) -> crate::Result<Self> {
     let jni_env = env.get_jni_env();
-    let array = jni_env.new_float_array(length as i32).unwrap();
+    let array = env.catch(jni_env.new_float_array(checked_length(length)?))?;
+    local_ref::track(&array, "Array::try_new");
 
-    FloatArray { env, array }
+    Ok(FloatArray { env, array })
   }
 
   fn from(env: &'a Env<'a>, array: JFloatArray<'a>) -> Self {
     FloatArray { env, array }
   }
 
-  fn length(&self) -> usize {
+  fn get_array(self) -> JFloatArray<'a> {
+    self.array
+  }
+
+  fn try_length(&self) -> crate::Result<usize> {
     let jni_env = self.env.get_jni_env();
-    jni_env.get_array_length(&self.array).unwrap() as usize
+    Ok(jni_env.get_array_length(&self.array)? as usize)
   }
 
-  fn get(&self, index: usize) -> Result<f32> {
+  fn get(&self, index: usize) -> crate::Result<f32> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let mut buf: [f32; 1] = [0.0];
-    jni_env.get_float_array_region(&self.array, index, &mut buf)?;
+    let operation = operation!("get_array_region", class = Some(String::from("[F")));
+    let result = self
+      .env
+      .catch(jni_env.get_float_array_region(&self.array, index, &mut buf));
+    operation.finish(&result);
+    result?;
 
     Ok(buf[0])
   }
 
-  fn set(&self, index: usize, value: f32) -> Result<()> {
+  fn set(&self, index: usize, value: f32) -> crate::Result<()> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let buf: [f32; 1] = [value];
-    jni_env.set_float_array_region(&self.array, index, &buf)?;
+    let operation = operation!("set_array_region", class = Some(String::from("[F")));
+    let result = self
+      .env
+      .catch(jni_env.set_float_array_region(&self.array, index, &buf));
+    operation.finish(&result);
+    result?;
 
     Ok(())
   }
@@ -271,38 +413,53 @@ pub struct IntArray<'a> {
 }
 
 impl<'a> Array<'a, i32, JIntArray<'a>> for IntArray<'a> {
-  fn new(env: &'a Env<'a>, length: usize) -> Self {
+  fn try_new(env: &'a Env<'a>, length: usize) -> crate::Result<Self> {
     let jni_env = env.get_jni_env();
-    let array = jni_env.new_int_array(length as i32).unwrap();
+    let array = env.catch(jni_env.new_int_array(checked_length(length)?))?;
+    local_ref::track(&array, "Array::try_new");
 
-    IntArray { env, array }
+    Ok(IntArray { env, array })
   }
 
   fn from(env: &'a Env<'a>, array: JIntArray<'a>) -> Self {
     IntArray { env, array }
   }
 
-  fn length(&self) -> usize {
+  fn get_array(self) -> JIntArray<'a> {
+    self.array
+  }
+
+  fn try_length(&self) -> crate::Result<usize> {
     let jni_env = self.env.get_jni_env();
-    jni_env.get_array_length(&self.array).unwrap() as usize
+    Ok(jni_env.get_array_length(&self.array)? as usize)
   }
 
-  fn get(&self, index: usize) -> Result<i32> {
+  fn get(&self, index: usize) -> crate::Result<i32> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let mut buf: [i32; 1] = [0];
-    jni_env.get_int_array_region(&self.array, index, &mut buf)?;
+    let operation = operation!("get_array_region", class = Some(String::from("[I")));
+    let result = self
+      .env
+      .catch(jni_env.get_int_array_region(&self.array, index, &mut buf));
+    operation.finish(&result);
+    result?;
 
     Ok(buf[0])
   }
 
-  fn set(&self, index: usize, value: i32) -> Result<()> {
+  fn set(&self, index: usize, value: i32) -> crate::Result<()> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let buf: [i32; 1] = [value];
-    jni_env.set_int_array_region(&self.array, index, &buf)?;
+    let operation = operation!("set_array_region", class = Some(String::from("[I")));
+    let result = self
+      .env
+      .catch(jni_env.set_int_array_region(&self.array, index, &buf));
+    operation.finish(&result);
+    result?;
 
     Ok(())
   }
@@ -315,38 +472,53 @@ pub struct LongArray<'a> {
 }
 
 impl<'a> Array<'a, i64, JLongArray<'a>> for LongArray<'a> {
-  fn new(env: &'a Env<'a>, length: usize) -> Self {
+  fn try_new(env: &'a Env<'a>, length: usize) -> crate::Result<Self> {
     let jni_env = env.get_jni_env();
-    let array = jni_env.new_long_array(length as i32).unwrap();
+    let array = env.catch(jni_env.new_long_array(checked_length(length)?))?;
+    local_ref::track(&array, "Array::try_new");
 
-    LongArray { env, array }
+    Ok(LongArray { env, array })
   }
 
   fn from(env: &'a Env<'a>, array: JLongArray<'a>) -> Self {
     LongArray { env, array }
   }
 
-  fn length(&self) -> usize {
+  fn get_array(self) -> JLongArray<'a> {
+    self.array
+  }
+
+  fn try_length(&self) -> crate::Result<usize> {
     let jni_env = self.env.get_jni_env();
-    jni_env.get_array_length(&self.array).unwrap() as usize
+    Ok(jni_env.get_array_length(&self.array)? as usize)
   }
 
-  fn get(&self, index: usize) -> Result<i64> {
+  fn get(&self, index: usize) -> crate::Result<i64> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let mut buf: [i64; 1] = [0];
-    jni_env.get_long_array_region(&self.array, index, &mut buf)?;
+    let operation = operation!("get_array_region", class = Some(String::from("[J")));
+    let result = self
+      .env
+      .catch(jni_env.get_long_array_region(&self.array, index, &mut buf));
+    operation.finish(&result);
+    result?;
 
     Ok(buf[0])
   }
 
-  fn set(&self, index: usize, value: i64) -> Result<()> {
+  fn set(&self, index: usize, value: i64) -> crate::Result<()> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let buf: [i64; 1] = [value];
-    jni_env.set_long_array_region(&self.array, index, &buf)?;
+    let operation = operation!("set_array_region", class = Some(String::from("[J")));
+    let result = self
+      .env
+      .catch(jni_env.set_long_array_region(&self.array, index, &buf));
+    operation.finish(&result);
+    result?;
 
     Ok(())
   }
@@ -359,38 +531,53 @@ pub struct ShortArray<'a> {
 }
 
 impl<'a> Array<'a, i16, JShortArray<'a>> for ShortArray<'a> {
-  fn new(env: &'a Env<'a>, length: usize) -> Self {
+  fn try_new(env: &'a Env<'a>, length: usize) -> crate::Result<Self> {
     let jni_env = env.get_jni_env();
-    let array = jni_env.new_short_array(length as i32).unwrap();
+    let array = env.catch(jni_env.new_short_array(checked_length(length)?))?;
+    local_ref::track(&array, "Array::try_new");
 
-    ShortArray { env, array }
+    Ok(ShortArray { env, array })
   }
 
   fn from(env: &'a Env<'a>, array: JShortArray<'a>) -> Self {
     ShortArray { env, array }
   }
 
-  fn length(&self) -> usize {
+  fn get_array(self) -> JShortArray<'a> {
+    self.array
+  }
+
+  fn try_length(&self) -> crate::Result<usize> {
     let jni_env = self.env.get_jni_env();
-    jni_env.get_array_length(&self.array).unwrap() as usize
+    Ok(jni_env.get_array_length(&self.array)? as usize)
   }
 
-  fn get(&self, index: usize) -> Result<i16> {
+  fn get(&self, index: usize) -> crate::Result<i16> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let mut buf: [i16; 1] = [0];
-    jni_env.get_short_array_region(&self.array, index, &mut buf)?;
+    let operation = operation!("get_array_region", class = Some(String::from("[S")));
+    let result = self
+      .env
+      .catch(jni_env.get_short_array_region(&self.array, index, &mut buf));
+    operation.finish(&result);
+    result?;
 
     Ok(buf[0])
   }
 
-  fn set(&self, index: usize, value: i16) -> Result<()> {
+  fn set(&self, index: usize, value: i16) -> crate::Result<()> {
     let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
     let buf: [i16; 1] = [value];
-    jni_env.set_short_array_region(&self.array, index, &buf)?;
+    let operation = operation!("set_array_region", class = Some(String::from("[S")));
+    let result = self
+      .env
+      .catch(jni_env.set_short_array_region(&self.array, index, &buf));
+    operation.finish(&result);
+    result?;
 
     Ok(())
   }
@@ -405,48 +592,93 @@ pub struct ObjectArray<'a> {
 impl<'a> ObjectArray<'a> {
   /// Create a new object array with the given length and class
   ///
+  /// Returns `Error::Exception` if the JVM throws, e.g. a `NoClassDefFoundError` for an unknown
+  /// class
+  ///
   /// # Arguments
   ///
   /// * `env` - The environment
   /// * `length` - The length of the array
   /// * `class` - The qualified class
-  pub fn new_with(env: &'a Env<'a>, length: usize, class: &str) -> Self {
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn try_new_with(env: &'a Env<'a>, length: usize, class: &str) -> crate::Result<Self> {
     let mut jni_env = env.get_jni_env();
-    let array = jni_env
-      .new_object_array(length as i32, class, JObject::null())
-      .unwrap();
+    let array =
+      env.catch(jni_env.new_object_array(checked_length(length)?, class, JObject::null()))?;
+    local_ref::track(&array, "ObjectArray::try_new_with");
 
-    ObjectArray { env, array }
+    Ok(ObjectArray { env, array })
+  }
+
+  /// Create a new object array with the given length and class
+  ///
+  /// This is a convenience for `try_new_with`
+  ///
+  /// # Panics
+  ///
+  /// Panics if the array cannot be created
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `length` - The length of the array
+  /// * `class` - The qualified class
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn new_with(env: &'a Env<'a>, length: usize, class: &str) -> Self {
+    Self::try_new_with(env, length, class).expect("Failed to create array")
   }
 }
 
 impl<'a> Array<'a, JObject<'a>, JObjectArray<'a>> for ObjectArray<'a> {
-  fn new(env: &'a Env<'a>, length: usize) -> Self {
-    Self::new_with(env, length, "java/lang/Object")
+  fn try_new(env: &'a Env<'a>, length: usize) -> crate::Result<Self> {
+    Self::try_new_with(env, length, "java/lang/Object")
   }
 
   fn from(env: &'a Env<'a>, array: JObjectArray<'a>) -> Self {
     ObjectArray { env, array }
   }
 
-  fn length(&self) -> usize {
+  fn get_array(self) -> JObjectArray<'a> {
+    self.array
+  }
+
+  fn try_length(&self) -> crate::Result<usize> {
     let jni_env = self.env.get_jni_env();
-    jni_env.get_array_length(&self.array).unwrap() as usize
+    Ok(jni_env.get_array_length(&self.array)? as usize)
   }
 
-  fn get(&self, index: usize) -> Result<JObject<'a>> {
+  fn get(&self, index: usize) -> crate::Result<JObject<'a>> {
     let mut jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let index = checked_index(self, index)?;
 
-    jni_env.get_object_array_element(&self.array, index)
-  }
+    let operation = operation!(
+      "get_array_element",
+      class = crate::trace::object_class_name(&mut jni_env, &self.array)
+    );
+    let result = self
+      .env
+      .catch(jni_env.get_object_array_element(&self.array, index));
+    operation.finish(&result);
 
-  fn set(&self, index: usize, value: JObject<'a>) -> Result<()> {
-    let jni_env = self.env.get_jni_env();
-    let index = index as i32;
+    let element = result?;
+    local_ref::track(&element, "ObjectArray::get");
 
-    jni_env.set_object_array_element(&self.array, index, value)?;
+    Ok(element)
+  }
 
-    Ok(())
+  fn set(&self, index: usize, value: JObject<'a>) -> crate::Result<()> {
+    let jni_env = self.env.get_jni_env();
+    let index = checked_index(self, index)?;
+
+    let operation = operation!(
+      "set_array_element",
+      class = crate::trace::object_class_name(&mut self.env.get_jni_env(), &self.array)
+    );
+    let result = self
+      .env
+      .catch(jni_env.set_object_array_element(&self.array, index, value));
+    operation.finish(&result);
+
+    result
   }
 }
@@ -0,0 +1,164 @@
+use crate::{env::Env, exception::StackTraceElement};
+use jni::objects::{JObject, JObjectArray, JValueGen};
+use std::backtrace::{Backtrace, BacktraceStatus};
+
+/// Converts a Rust backtrace into stack trace elements, innermost frame first
+///
+/// Frames of the standard library are left out. Returns no elements if the backtrace was not
+/// captured.
+///
+/// # Arguments
+///
+/// * `backtrace` - The backtrace
+pub fn rust_stack_trace(backtrace: &Backtrace) -> Vec<StackTraceElement> {
+  if backtrace.status() != BacktraceStatus::Captured {
+    return Vec::new();
+  }
+
+  parse_backtrace(&backtrace.to_string())
+}
+
+/// Parses the `Display` output of a captured backtrace
+///
+/// Each frame is a line like `12: module::function`, optionally followed by a line like
+/// `at ./src/file.rs:10:5`
+///
+/// # Arguments
+///
+/// * `text` - The `Display` output of the backtrace
+pub(crate) fn parse_backtrace(text: &str) -> Vec<StackTraceElement> {
+  let mut frames: Vec<StackTraceElement> = Vec::new();
+
+  for line in text.lines().map(str::trim) {
+    if let Some(location) = line.strip_prefix("at ") {
+      // The location is `file:line:column`, where the file may itself contain colons
+      let mut parts = location.rsplitn(3, ':');
+      let (_, line, file) = (parts.next(), parts.next(), parts.next());
+
+      if let (Some(frame), Some(line), Some(file)) = (frames.last_mut(), line, file) {
+        let file = file.rsplit(['/', '\\']).next().unwrap_or(file);
+
+        frame.file = Some(file.into());
+        frame.line = line.parse().unwrap_or(-1);
+      }
+
+      continue;
+    }
+
+    // Inlined functions share the number of their frame, which is only printed once
+    let function = match line.split_once(": ") {
+      Some((number, function)) if number.parse::<usize>().is_ok() => function,
+      _ => line,
+    };
+
+    if function.is_empty() {
+      continue;
+    }
+
+    let (class, method) = function.rsplit_once("::").unwrap_or(("", function));
+
+    frames.push(StackTraceElement {
+      class: class.into(),
+      method: method.into(),
+      file: None,
+      line: -1,
+    });
+  }
+
+  frames.retain(|frame| !is_internal(frame));
+  frames
+}
+
+/// Checks whether a frame belongs to the standard library or to jnat's panic handling
+fn is_internal(frame: &StackTraceElement) -> bool {
+  const PREFIXES: &[&str] = &[
+    "std::",
+    "core::",
+    "alloc::",
+    "__rust",
+    "rust_begin_unwind",
+    "<unknown>",
+    "jnat::panic::",
+  ];
+
+  let class = frame.class.trim_start_matches('<');
+  let function = if class.is_empty() {
+    frame.method.clone()
+  } else {
+    format!("{}::{}", class, frame.method)
+  };
+
+  PREFIXES.iter().any(|prefix| function.starts_with(prefix))
+}
+
+/// Adds frames in front of the stack trace of a throwable
+///
+/// # Arguments
+///
+/// * `env` - The environment
+/// * `throwable` - The throwable
+/// * `frames` - The frames, innermost first
+pub(crate) fn splice_stack_trace(
+  env: &Env,
+  throwable: &JObject,
+  frames: &[StackTraceElement],
+) -> crate::Result<()> {
+  const ELEMENT: &str = "java/lang/StackTraceElement";
+
+  let mut jni_env = env.get_jni_env();
+
+  let stack_trace = jni_env
+    .call_method(
+      throwable,
+      "getStackTrace",
+      "()[Ljava/lang/StackTraceElement;",
+      &[],
+    )?
+    .l()?;
+  let stack_trace = jni_env.auto_local(JObjectArray::from(stack_trace));
+  let length = jni_env.get_array_length(&*stack_trace)?;
+
+  let spliced = jni_env.new_object_array(frames.len() as i32 + length, ELEMENT, JObject::null())?;
+  let spliced = jni_env.auto_local(spliced);
+
+  for (i, frame) in frames.iter().enumerate() {
+    let class = jni_env.new_string(&frame.class)?;
+    let method = jni_env.new_string(&frame.method)?;
+    let file = match &frame.file {
+      Some(file) => JObject::from(jni_env.new_string(file)?),
+      None => JObject::null(),
+    };
+
+    let element = env.catch(jni_env.new_object(
+      ELEMENT,
+      "(Ljava/lang/String;Ljava/lang/String;Ljava/lang/String;I)V",
+      &[
+        (&class).into(),
+        (&method).into(),
+        (&file).into(),
+        JValueGen::Int(frame.line),
+      ],
+    ))?;
+    jni_env.set_object_array_element(&*spliced, i as i32, &element)?;
+
+    jni_env.delete_local_ref(element)?;
+    jni_env.delete_local_ref(class)?;
+    jni_env.delete_local_ref(method)?;
+    jni_env.delete_local_ref(file)?;
+  }
+
+  for i in 0..length {
+    let element = jni_env.get_object_array_element(&*stack_trace, i)?;
+    jni_env.set_object_array_element(&*spliced, frames.len() as i32 + i, &element)?;
+    jni_env.delete_local_ref(element)?;
+  }
+
+  env.catch(jni_env.call_method(
+    throwable,
+    "setStackTrace",
+    "([Ljava/lang/StackTraceElement;)V",
+    &[(&spliced).into()],
+  ))?;
+
+  Ok(())
+}
@@ -0,0 +1,212 @@
+use crate::{env::Env, error::Error, local_ref};
+use jni::objects::{GlobalRef, JByteBuffer, JObject};
+use std::{
+  ops::DerefMut,
+  slice,
+  sync::{Mutex, PoisonError},
+};
+
+/// Memory given to Java by `DirectBuffer::from_memory`
+type Memory = Box<dyn DerefMut<Target = [u8]> + Send>;
+
+/// The memory given to Java, freed once the buffers using it are garbage collected
+static REGISTRY: Mutex<Option<Registry>> = Mutex::new(None);
+
+struct Registry {
+  /// The `java.lang.ref.ReferenceQueue` that collected buffers are added to
+  queue: GlobalRef,
+  /// A `java.lang.ref.PhantomReference` to each buffer, and its memory
+  buffers: Vec<(GlobalRef, Memory)>,
+}
+
+/// A struct wrapping a direct `java.nio.ByteBuffer`, whose memory can be shared with Rust
+///
+/// Memory given to Java with `from_memory` is not freed when its buffer is garbage collected, but
+/// by the next call to `from_memory` or `release_unreachable` on any thread, so call
+/// `release_unreachable` regularly if buffers are created rarely.
+pub struct DirectBuffer<'a> {
+  env: &'a Env<'a>,
+  buffer: JByteBuffer<'a>,
+}
+
+impl<'a> DirectBuffer<'a> {
+  /// Creates a new DirectBuffer, e.g. for a buffer created with `ByteBuffer.allocateDirect`
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `buffer` - The JByteBuffer to wrap
+  pub fn new(env: &'a Env<'a>, buffer: JByteBuffer<'a>) -> DirectBuffer<'a> {
+    DirectBuffer { env, buffer }
+  }
+
+  /// Creates a direct buffer using Rust memory, such as a `Vec<u8>`, `Box<[u8]>` or a
+  /// memory-mapped region, without copying it
+  ///
+  /// The memory is kept alive until the buffer is garbage collected, and is then freed by the next
+  /// call to `from_memory` or `release_unreachable`
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `memory` - The memory
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn from_memory<T>(env: &'a Env<'a>, memory: T) -> crate::Result<DirectBuffer<'a>>
+  where
+    T: DerefMut<Target = [u8]> + Send + 'static,
+  {
+    DirectBuffer::release_unreachable(env)?;
+
+    // The memory is boxed first, so that the address given to Java does not move
+    let mut memory: Memory = Box::new(memory);
+    let (data, length) = (memory.as_mut_ptr(), memory.len());
+
+    let mut jni_env = env.get_jni_env();
+    let buffer = env.catch(unsafe { jni_env.new_direct_byte_buffer(data, length) })?;
+    local_ref::track(&buffer, "DirectBuffer::from_memory");
+
+    let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+    let registry = match registry.as_mut() {
+      Some(registry) => registry,
+      None => {
+        let queue = jni_env.new_object("java/lang/ref/ReferenceQueue", "()V", &[]);
+        let queue = jni_env.new_global_ref(env.catch(queue)?)?;
+
+        registry.insert(Registry {
+          queue,
+          buffers: Vec::new(),
+        })
+      }
+    };
+
+    let reference = jni_env.new_object(
+      "java/lang/ref/PhantomReference",
+      "(Ljava/lang/Object;Ljava/lang/ref/ReferenceQueue;)V",
+      &[(&buffer).into(), registry.queue.as_obj().into()],
+    );
+    let reference = env.catch(reference)?;
+
+    registry
+      .buffers
+      .push((jni_env.new_global_ref(&reference)?, memory));
+    jni_env.delete_local_ref(reference)?;
+
+    Ok(DirectBuffer::new(env, buffer))
+  }
+
+  /// Frees the memory of buffers created with `from_memory` that were garbage collected
+  ///
+  /// Returns the number of buffers whose memory was freed
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  pub fn release_unreachable(env: &Env) -> crate::Result<usize> {
+    let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+    let Some(registry) = registry.as_mut() else {
+      return Ok(0);
+    };
+
+    let mut jni_env = env.get_jni_env();
+    let mut released = 0;
+
+    loop {
+      let reference = jni_env.call_method(
+        registry.queue.as_obj(),
+        "poll",
+        "()Ljava/lang/ref/Reference;",
+        &[],
+      );
+      let reference = env.catch(reference)?.l()?;
+
+      if reference.is_null() {
+        return Ok(released);
+      }
+
+      let position = registry.buffers.iter().position(|(buffer, _)| {
+        jni_env
+          .is_same_object(buffer.as_obj(), &reference)
+          .unwrap_or(false)
+      });
+      jni_env.delete_local_ref(reference)?;
+
+      if let Some(position) = position {
+        registry.buffers.swap_remove(position);
+        released += 1;
+      }
+    }
+  }
+
+  /// Gets the capacity of the buffer in bytes
+  ///
+  /// Returns a `NullPtr` error if the buffer is not direct
+  pub fn capacity(&self) -> crate::Result<usize> {
+    let jni_env = self.env.get_jni_env();
+
+    jni_env.get_direct_buffer_address(&self.buffer)?;
+    Ok(jni_env.get_direct_buffer_capacity(&self.buffer)?)
+  }
+
+  /// Borrows the memory of the buffer, without copying it
+  ///
+  /// Returns a `NullPtr` error if the buffer is not direct
+  ///
+  /// # Safety
+  ///
+  /// Nothing may write to the buffer while the slice is borrowed, including Java and other
+  /// DirectBuffers wrapping the same buffer
+  pub unsafe fn as_slice(&self) -> crate::Result<&[u8]> {
+    let (data, capacity) = self.memory()?;
+
+    if capacity == 0 {
+      return Ok(&[]);
+    }
+
+    Ok(unsafe { slice::from_raw_parts(data, capacity) })
+  }
+
+  /// Borrows the memory of the buffer mutably, without copying it
+  ///
+  /// Returns `Error::ReadOnlyBuffer` if the buffer is read-only, or a `NullPtr` error if the
+  /// buffer is not direct
+  ///
+  /// # Safety
+  ///
+  /// Nothing else may access the buffer while the slice is borrowed, including Java and other
+  /// DirectBuffers wrapping the same buffer
+  pub unsafe fn as_mut_slice(&mut self) -> crate::Result<&mut [u8]> {
+    let (data, capacity) = self.memory()?;
+
+    let mut jni_env = self.env.get_jni_env();
+    let read_only = jni_env.call_method(&self.buffer, "isReadOnly", "()Z", &[]);
+    if self.env.catch(read_only)?.z()? {
+      return Err(Error::ReadOnlyBuffer);
+    }
+
+    if capacity == 0 {
+      return Ok(&mut []);
+    }
+
+    Ok(unsafe { slice::from_raw_parts_mut(data, capacity) })
+  }
+
+  /// Gets the wrapped buffer
+  pub fn get_buffer(&self) -> &JByteBuffer<'a> {
+    &self.buffer
+  }
+
+  /// Gets the wrapped buffer as an object
+  pub fn get_object(&self) -> &JObject<'a> {
+    &self.buffer
+  }
+
+  /// Gets the address and capacity of the buffer
+  fn memory(&self) -> crate::Result<(*mut u8, usize)> {
+    let jni_env = self.env.get_jni_env();
+
+    let data = jni_env.get_direct_buffer_address(&self.buffer)?;
+    let capacity = jni_env.get_direct_buffer_capacity(&self.buffer)?;
+
+    Ok((data, capacity))
+  }
+}
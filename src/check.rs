@@ -0,0 +1,137 @@
+use crate::{r#type::Type, value::Value};
+use jni::{errors::Result, JNIEnv};
+
+/// Whether jnat checks JNI usage before making calls
+///
+/// Checks are made in debug builds, or with the `checked` feature. A failed check is returned as
+/// `Error::Check` instead of reaching the JVM, which may crash on invalid JNI usage.
+pub(crate) const CHECKED: bool = cfg!(any(feature = "checked", debug_assertions));
+
+/// Checks that a call may be made with the given arguments
+///
+/// Returns the reason the call is invalid, if it is
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `types` - The types of the parameters
+/// * `args` - The arguments
+pub(crate) fn call(jni_env: &mut JNIEnv, types: &[Type], args: &[Value]) -> Result<Option<String>> {
+  if let Some(reason) = pending_exception(jni_env)? {
+    return Ok(Some(reason));
+  }
+
+  if types.len() != args.len() {
+    return Ok(Some(format!(
+      "expected {} arguments, got {}",
+      types.len(),
+      args.len()
+    )));
+  }
+
+  for (i, (r#type, value)) in types.iter().zip(args).enumerate() {
+    if let Some(reason) = value_of_type(jni_env, *r#type, *value)? {
+      return Ok(Some(format!("argument {}: {}", i + 1, reason)));
+    }
+  }
+
+  Ok(None)
+}
+
+/// Checks that no Java exception is pending, as most JNI functions must not be called then
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+pub(crate) fn pending_exception(jni_env: &JNIEnv) -> Result<Option<String>> {
+  Ok(
+    jni_env
+      .exception_check()?
+      .then(|| "a Java exception is pending".into()),
+  )
+}
+
+/// Checks that a value can be used where a type is expected
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `type` - The expected type
+/// * `value` - The value
+pub(crate) fn value_of_type(
+  jni_env: &mut JNIEnv,
+  r#type: Type,
+  value: Value,
+) -> Result<Option<String>> {
+  let matches = match (r#type, value) {
+    (_, Value::Void) | (Type::Void, _) => {
+      return Ok(Some("void is not a value".into()));
+    }
+    (Type::Boolean, Value::Boolean(_))
+    | (Type::Byte, Value::Byte(_))
+    | (Type::Char, Value::Char(_))
+    | (Type::Short, Value::Short(_))
+    | (Type::Int, Value::Int(_))
+    | (Type::Long, Value::Long(_))
+    | (Type::Float, Value::Float(_))
+    | (Type::Double, Value::Double(_)) => true,
+    (Type::Object(_) | Type::Array(_), Value::Object(_) | Value::String(_)) => {
+      let object = value
+        .as_object()
+        .expect("objects and strings have an object");
+      if object.is_null() {
+        return Ok(None);
+      }
+
+      let class = match r#type {
+        Type::Object(name) => name.to_string(),
+        _ => String::from(r#type),
+      };
+
+      // A class that cannot be found is reported by the call itself
+      match jni_env.is_instance_of(object, class) {
+        Ok(is_instance) => is_instance,
+        Err(jni::errors::Error::JavaException) => {
+          jni_env.exception_clear()?;
+          true
+        }
+        Err(error) => return Err(error),
+      }
+    }
+    _ => false,
+  };
+
+  if matches {
+    return Ok(None);
+  }
+
+  Ok(Some(format!(
+    "expected {}, got {}",
+    r#type,
+    describe(jni_env, value)?
+  )))
+}
+
+/// Describes the kind of a value, or the class of an object
+fn describe(jni_env: &mut JNIEnv, value: Value) -> Result<String> {
+  let description = match value {
+    Value::Boolean(_) => "boolean",
+    Value::Byte(_) => "byte",
+    Value::Char(_) => "char",
+    Value::Short(_) => "short",
+    Value::Int(_) => "int",
+    Value::Long(_) => "long",
+    Value::Float(_) => "float",
+    Value::Double(_) => "double",
+    Value::Void => "void",
+    Value::String(_) => "a java.lang.String",
+    Value::Object(object) => {
+      let class = jni_env.get_object_class(object.get_object())?;
+      let class = jni_env.auto_local(class);
+
+      return crate::reflection::get_name(jni_env, &class).map(|name| format!("a {}", name));
+    }
+  };
+
+  Ok(description.into())
+}
@@ -1,5 +1,16 @@
-use crate::{env::Env, signature::Signature, value::Value, Type};
-use jni::objects::{JClass, JObject, JStaticFieldID, JValueGen};
+use crate::{
+  check,
+  env::Env,
+  local_ref,
+  object::Object,
+  reflection::{self, get_name},
+  signature::Signature,
+  trace::operation,
+  value::{ReturnValue, Value},
+  Type,
+};
+use jni::objects::{JClass, JObject, JObjectArray, JStaticFieldID, JValueGen};
+use std::collections::HashMap;
 
 /// A struct wrapping a JClass
 pub struct Class<'a> {
@@ -20,53 +31,177 @@ impl<'a> Class<'a> {
 
   /// Calls a static method on the class
   ///
+  /// Returns `Error::Exception` if the method throws
+  ///
   /// # Arguments
   ///
   /// * `name` - The name of the method
   /// * `signature` - The signature of the method
   /// * `args` - The arguments to pass to the method
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn call_static_method(
     &self,
     name: &str,
     signature: Signature,
     args: &[Value],
-  ) -> jni::errors::Result<JValueGen<JObject<'_>>> {
+  ) -> crate::Result<JValueGen<JObject<'_>>> {
     let class = &self.class;
-    let signature: String = signature.into();
+    let descriptor: String = signature.into();
+    let member = |_: &str| format!("static {}", signature.declaration(name));
 
     let mut jni_env = self.env.get_jni_env();
-    jni_env.call_static_method(
+    if check::CHECKED {
+      self.check_member(member, check::call(&mut jni_env, signature.arguments, args))?;
+    }
+
+    let operation = operation!(
+      "call_static_method",
+      class = crate::trace::class_name(&mut jni_env, class),
+      member = name,
+      descriptor = descriptor
+    );
+    let result = jni_env.call_static_method(
       class,
       name,
-      signature,
+      &descriptor,
       args
         .iter()
         .map(|o| self.env.new_value(*o))
         .collect::<Vec<JValueGen<&JObject>>>()
         .as_slice(),
-    )
+    );
+    operation.finish(&result);
+
+    let value = self.catch_member(member, (name, &descriptor), result)?;
+    local_ref::track_value(&value, "Class::call_static_method");
+
+    Ok(value)
+  }
+
+  /// Calls a static method on the class, returning a value typed by the return type of the
+  /// signature
+  ///
+  /// Strings are returned as `ReturnValue::String`. Returns `Error::Exception` if the method
+  /// throws.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The name of the method
+  /// * `signature` - The signature of the method
+  /// * `args` - The arguments to pass to the method
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn call_static_method_typed(
+    &self,
+    name: &str,
+    signature: Signature,
+    args: &[Value],
+  ) -> crate::Result<ReturnValue<'_>> {
+    let value = self.call_static_method(name, signature, args)?;
+    Ok(ReturnValue::from_jvalue(
+      self.env,
+      value,
+      signature.return_type,
+    ))
   }
 
   /// Creates an instance of the class
   ///
+  /// Returns `Error::Exception` if the constructor throws
+  ///
   /// # Arguments
   ///
   /// * `signature` - The signature of the constructor
   /// * `args` - The arguments to pass to the constructor
-  pub fn create(&self, signature: Signature, args: &[Value]) -> jni::errors::Result<JObject> {
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn create(&self, signature: Signature, args: &[Value]) -> crate::Result<JObject<'_>> {
     let class = &self.class;
-    let signature: String = signature.into();
+    let descriptor: String = signature.into();
+    let member = |class: &str| signature.constructor_declaration(class);
 
     let mut jni_env = self.env.get_jni_env();
-    jni_env.new_object(
+    if check::CHECKED {
+      self.check_member(member, check::call(&mut jni_env, signature.arguments, args))?;
+    }
+
+    let operation = operation!(
+      "new_object",
+      class = crate::trace::class_name(&mut jni_env, class),
+      member = "<init>",
+      descriptor = descriptor
+    );
+    let result = jni_env.new_object(
       class,
-      signature,
+      &descriptor,
       args
         .iter()
         .map(|o| self.env.new_value(*o))
         .collect::<Vec<JValueGen<&JObject>>>()
         .as_slice(),
-    )
+    );
+    operation.finish(&result);
+
+    let object = self.catch_member(member, ("<init>", &descriptor), result)?;
+    local_ref::track(&object, "Class::create");
+
+    Ok(object)
+  }
+
+  /// Creates an instance of the class, if it is an inner, anonymous or local class
+  ///
+  /// javac passes the enclosing instance as a hidden first constructor argument, which this adds
+  /// to the signature and arguments unless the class is declared in a static context, like a
+  /// static nested class or a local class in a static method. Captured local variables are not
+  /// added. Returns `Error::Check` if the class is a top-level class, and `Error::Exception` if
+  /// the constructor throws.
+  ///
+  /// # Arguments
+  ///
+  /// * `outer` - The enclosing instance, ignored in a static context
+  /// * `signature` - The signature of the constructor, without the enclosing instance
+  /// * `args` - The arguments to pass to the constructor, without the enclosing instance
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn create_inner(
+    &self,
+    outer: &Object,
+    signature: Signature,
+    args: &[Value],
+  ) -> crate::Result<JObject<'_>> {
+    let class = &self.class;
+
+    let mut jni_env = self.env.get_jni_env();
+    let enclosing = jni_env
+      .call_method(class, "getEnclosingClass", "()Ljava/lang/Class;", &[])?
+      .l()?;
+    let enclosing = jni_env.auto_local(enclosing);
+
+    if enclosing.is_null() {
+      let class = get_name(&mut jni_env, class)?;
+
+      return Err(crate::Error::Check {
+        member: signature.constructor_declaration(&class),
+        class,
+        message: "a top-level class has no enclosing instance".into(),
+      });
+    }
+
+    if !reflection::has_enclosing_instance(&mut jni_env, class)? {
+      return self.create(signature, args);
+    }
+
+    let enclosing = get_name(&mut jni_env, &enclosing)?.replace('.', "/");
+
+    let arguments = [Type::Object(&enclosing)]
+      .iter()
+      .chain(signature.arguments)
+      .copied()
+      .collect::<Vec<Type>>();
+    let args = [Value::Object(*outer)]
+      .iter()
+      .chain(args)
+      .copied()
+      .collect::<Vec<Value>>();
+
+    self.create(Signature::new(&arguments, signature.return_type), &args)
   }
 
   /// Gets a static field on the class
@@ -75,16 +210,34 @@ impl<'a> Class<'a> {
   ///
   /// * `name` - The name of the field
   /// * `type` - The type of the field
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn get_static_field(
     &self,
     name: &str,
     r#type: Type,
-  ) -> jni::errors::Result<JValueGen<JObject<'_>>> {
+  ) -> crate::Result<JValueGen<JObject<'_>>> {
     let class = &self.class;
-    let r#type: String = r#type.into();
+    let descriptor: String = r#type.into();
+    let member = |_: &str| format!("static {} {}", r#type, name);
 
     let mut jni_env = self.env.get_jni_env();
-    jni_env.get_static_field(class, name, r#type)
+    if check::CHECKED {
+      self.check_member(member, check::pending_exception(&jni_env))?;
+    }
+
+    let operation = operation!(
+      "get_static_field",
+      class = crate::trace::class_name(&mut jni_env, class),
+      member = name,
+      descriptor = descriptor
+    );
+    let result = jni_env.get_static_field(class, name, &descriptor);
+    operation.finish(&result);
+
+    let value = self.catch_member(member, (name, &descriptor), result)?;
+    local_ref::track_value(&value, "Class::get_static_field");
+
+    Ok(value)
   }
 
   /// Sets a static field on the class
@@ -93,18 +246,27 @@ impl<'a> Class<'a> {
   ///
   /// * `name` - The name of the field
   /// * `value` - The value to set the field to
-  pub fn set_static_field(
-    &self,
-    name: &str,
-    r#type: Type,
-    value: Value,
-  ) -> jni::errors::Result<()> {
+  pub fn set_static_field(&self, name: &str, r#type: Type, value: Value) -> crate::Result<()> {
     let class = &self.class;
-    let value = self.env.new_value(value);
-    let field = self.get_static_field_id(name, r#type)?;
+    let member = |_: &str| format!("static {} {}", r#type, name);
 
     let mut jni_env = self.env.get_jni_env();
-    jni_env.set_static_field(class, field, value)
+    if check::CHECKED {
+      self.check_member(member, check::pending_exception(&jni_env))?;
+      self.check_member(member, check::value_of_type(&mut jni_env, r#type, value))?;
+    }
+
+    let field = self.get_static_field_id(name, r#type)?;
+    let operation = operation!(
+      "set_static_field",
+      class = crate::trace::class_name(&mut jni_env, class),
+      member = name,
+      descriptor = String::from(r#type)
+    );
+    let result = jni_env.set_static_field(class, field, self.env.new_value(value));
+    operation.finish(&result);
+
+    self.catch_member(member, (name, &String::from(r#type)), result)
   }
 
   /// Get a static field ID on the class
@@ -113,20 +275,189 @@ impl<'a> Class<'a> {
   ///
   /// * `name` - The name of the field
   /// * `signature` - The signature of the field
-  pub fn get_static_field_id(
-    &self,
-    name: &str,
-    r#type: Type,
-  ) -> jni::errors::Result<JStaticFieldID> {
+  pub fn get_static_field_id(&self, name: &str, r#type: Type) -> crate::Result<JStaticFieldID> {
+    let class = &self.class;
+    let descriptor: String = r#type.into();
+
+    let mut jni_env = self.env.get_jni_env();
+    let result = jni_env.get_static_field_id(class, name, &descriptor);
+
+    self.catch_member(
+      |_| format!("static {} {}", r#type, name),
+      (name, &descriptor),
+      result,
+    )
+  }
+
+  /// Runs the static initializer of the class, if it has not run yet
+  ///
+  /// Returns `Error::Exception` if the static initializer throws
+  pub fn ensure_initialized(&self) -> crate::Result<()> {
+    let class = &self.class;
+
+    let mut jni_env = self.env.get_jni_env();
+    let operation = operation!(
+      "ensure_initialized",
+      class = crate::trace::class_name(&mut jni_env, class)
+    );
+    let is_primitive = jni_env.call_method(class, "isPrimitive", "()Z", &[]);
+    if self.env.catch(is_primitive)?.z()? {
+      let result = Ok(());
+      operation.finish(&result);
+
+      return result;
+    }
+
+    let name = jni_env.call_method(class, "getName", "()Ljava/lang/String;", &[]);
+    let name = jni_env.auto_local(self.env.catch(name)?.l()?);
+    let loader = jni_env.call_method(class, "getClassLoader", "()Ljava/lang/ClassLoader;", &[]);
+    let loader = jni_env.auto_local(self.env.catch(loader)?.l()?);
+
+    // Class.forName initializes the class when asked to
+    let result = jni_env.call_static_method(
+      "java/lang/Class",
+      "forName",
+      "(Ljava/lang/String;ZLjava/lang/ClassLoader;)Ljava/lang/Class;",
+      &[(&*name).into(), true.into(), (&*loader).into()],
+    );
+    let initialized = self.env.catch(result)?.l()?;
+    jni_env.delete_local_ref(initialized)?;
+
+    let result = Ok(());
+    operation.finish(&result);
+
+    result
+  }
+
+  /// Gets the constants of the class, if it is an enum
+  ///
+  /// Returns a `NullPtr` error if the class is not an enum
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn enum_constants(&self) -> crate::Result<Vec<JObject<'_>>> {
+    let class = &self.class;
+
+    let mut jni_env = self.env.get_jni_env();
+    let constants = jni_env
+      .call_method(class, "getEnumConstants", "()[Ljava/lang/Object;", &[])?
+      .l()?;
+
+    if constants.is_null() {
+      return Err(jni::errors::Error::NullPtr("getEnumConstants").into());
+    }
+
+    let constants = JObjectArray::from(constants);
+    let length = jni_env.get_array_length(&constants)?;
+
+    let constants = (0..length)
+      .map(|i| jni_env.get_object_array_element(&constants, i))
+      .collect::<jni::errors::Result<Vec<JObject>>>()?;
+
+    for constant in &constants {
+      local_ref::track(constant, "Class::enum_constants");
+    }
+
+    Ok(constants)
+  }
+
+  /// Gets every static final primitive and String field declared by the class, keyed by name
+  ///
+  /// Strings are returned as `ReturnValue::String`, unless they are null. Synthetic fields, such as
+  /// `$assertionsDisabled`, are skipped.
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn constants(&self) -> crate::Result<HashMap<String, ReturnValue<'a>>> {
+    // java.lang.reflect.Modifier.STATIC | java.lang.reflect.Modifier.FINAL
+    const STATIC_FINAL: i32 = 0x0008 | 0x0010;
+    // The package-private java.lang.reflect.Modifier.SYNTHETIC
+    const SYNTHETIC: i32 = 0x1000;
+
     let class = &self.class;
-    let r#type: String = r#type.into();
 
     let mut jni_env = self.env.get_jni_env();
-    jni_env.get_static_field_id(class, name, r#type)
+    let fields = JObjectArray::from(
+      jni_env
+        .call_method(
+          class,
+          "getDeclaredFields",
+          "()[Ljava/lang/reflect/Field;",
+          &[],
+        )?
+        .l()?,
+    );
+    let fields = jni_env.auto_local(fields);
+
+    let mut constants = HashMap::new();
+
+    for i in 0..jni_env.get_array_length(&*fields)? {
+      let field = jni_env.get_object_array_element(&*fields, i)?;
+      let field = jni_env.auto_local(field);
+
+      let modifiers = jni_env
+        .call_method(&field, "getModifiers", "()I", &[])?
+        .i()?;
+      if modifiers & STATIC_FINAL != STATIC_FINAL || modifiers & SYNTHETIC != 0 {
+        continue;
+      }
+
+      let field_type = jni_env
+        .call_method(&field, "getType", "()Ljava/lang/Class;", &[])?
+        .l()?;
+      let field_type = jni_env.auto_local(field_type);
+      let field_type = match get_name(&mut jni_env, &field_type)?.as_str() {
+        "boolean" => Type::Boolean,
+        "byte" => Type::Byte,
+        "char" => Type::Char,
+        "short" => Type::Short,
+        "int" => Type::Int,
+        "long" => Type::Long,
+        "float" => Type::Float,
+        "double" => Type::Double,
+        "java.lang.String" => Type::Object("java/lang/String"),
+        _ => continue,
+      };
+
+      let name = get_name(&mut jni_env, &field)?;
+      let descriptor: String = field_type.into();
+      let value = jni_env.get_static_field(class, &name, descriptor)?;
+      local_ref::track_value(&value, "Class::constants");
+      constants.insert(name, ReturnValue::from_jvalue(self.env, value, field_type));
+    }
+
+    Ok(constants)
   }
 
   /// Get the wrapped class
   pub fn get_class(self) -> JClass<'a> {
     self.class
   }
+
+  /// Borrows the wrapped class
+  pub(crate) fn as_class(&self) -> &JClass<'a> {
+    &self.class
+  }
+
+  /// Adds the class and member that were accessed to the error of a JNI call
+  fn catch_member<T>(
+    &self,
+    member: impl FnOnce(&str) -> String,
+    lookup: (&str, &str),
+    result: jni::errors::Result<T>,
+  ) -> crate::Result<T> {
+    self.env.catch_member(
+      |jni_env| get_name(jni_env, &self.class),
+      member,
+      lookup,
+      result,
+    )
+  }
+
+  /// Adds the class and member that were accessed to a failed check
+  fn check_member(
+    &self,
+    member: impl FnOnce(&str) -> String,
+    check: jni::errors::Result<Option<String>>,
+  ) -> crate::Result<()> {
+    self
+      .env
+      .check_member(|jni_env| get_name(jni_env, &self.class), member, check)
+  }
 }
@@ -0,0 +1,154 @@
+use crate::{env::Env, value::ReturnValue, Result, Type};
+use jni::errors::Error;
+use std::collections::HashMap;
+
+/// A trait for structs mirroring the constants of a Java class
+///
+/// This is usually implemented with `#[derive(JavaConstants)]` from `jnat_macros`
+pub trait JavaConstants: Sized {
+  /// The qualified name of the Java class
+  const CLASS: &'static str;
+
+  /// The names and types of the expected constants
+  const CONSTANTS: &'static [(&'static str, Type<'static>)];
+
+  /// Creates the struct from the constants of the class
+  ///
+  /// Every constant in `CONSTANTS` is checked to be present before this is called by `load`
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `constants` - The constants of the class, as returned by `Class::constants`
+  fn from_constants<'a>(
+    env: &'a Env<'a>,
+    constants: HashMap<String, ReturnValue<'a>>,
+  ) -> Result<Self>;
+
+  /// Loads the constants from the class
+  ///
+  /// Returns `Error::FieldNotFound` if an expected constant does not exist
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  fn load<'a>(env: &'a Env<'a>) -> Result<Self> {
+    let class = env.get_class(Self::CLASS)?;
+    let constants = class.constants();
+    // Only the expected constants reach from_constants, so the other references are deleted
+    env.delete_local_ref(class.get_class())?;
+
+    let (constants, other): (HashMap<_, _>, HashMap<_, _>) = constants?
+      .into_iter()
+      .partition(|(name, _)| Self::CONSTANTS.iter().any(|(expected, _)| expected == name));
+    for value in other.into_values() {
+      delete_value(env, value)?;
+    }
+
+    if let Some((name, r#type)) = Self::CONSTANTS
+      .iter()
+      .find(|(name, _)| !constants.contains_key(*name))
+    {
+      for value in constants.into_values() {
+        delete_value(env, value)?;
+      }
+
+      return Err(
+        Error::FieldNotFound {
+          name: name.to_string(),
+          sig: (*r#type).into(),
+        }
+        .into(),
+      );
+    }
+
+    Self::from_constants(env, constants)
+  }
+}
+
+/// Deletes the local reference of a constant, if it is an object
+///
+/// # Arguments
+///
+/// * `env` - The environment
+/// * `value` - The value of the constant
+fn delete_value(env: &Env, value: ReturnValue) -> Result<()> {
+  match value {
+    ReturnValue::String(string) => env.delete_local_ref(string.into_string()),
+    ReturnValue::Object(object) => env.delete_local_ref(object),
+    _ => Ok(()),
+  }
+}
+
+/// A trait for Rust types that can hold the value of a Java constant
+pub trait FromConstant<'a>: Sized {
+  /// The Java type of the constant
+  const TYPE: Type<'static>;
+
+  /// Converts the value of a constant
+  ///
+  /// Returns `Error::WrongJValueType` if the value does not have the type of the constant
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `value` - The value of the constant
+  fn from_constant(env: &'a Env<'a>, value: ReturnValue<'a>) -> Result<Self>;
+}
+
+macro_rules! from_constant {
+  ($rust:ty, $java:expr, $name:literal, $variant:ident) => {
+    impl<'a> FromConstant<'a> for $rust {
+      const TYPE: Type<'static> = $java;
+
+      fn from_constant(_: &'a Env<'a>, value: ReturnValue<'a>) -> Result<Self> {
+        match value {
+          ReturnValue::$variant(value) => Ok(value),
+          value => Err(Error::WrongJValueType($name, value.type_name()).into()),
+        }
+      }
+    }
+  };
+}
+
+from_constant!(bool, Type::Boolean, "bool", Boolean);
+from_constant!(i8, Type::Byte, "byte", Byte);
+from_constant!(i16, Type::Short, "short", Short);
+from_constant!(i32, Type::Int, "int", Int);
+from_constant!(i64, Type::Long, "long", Long);
+from_constant!(f32, Type::Float, "float", Float);
+from_constant!(f64, Type::Double, "double", Double);
+
+impl<'a> FromConstant<'a> for char {
+  const TYPE: Type<'static> = Type::Char;
+
+  fn from_constant(_: &'a Env<'a>, value: ReturnValue<'a>) -> Result<Self> {
+    match value {
+      ReturnValue::Char(unit) => {
+        char::from_u32(unit as u32).ok_or(crate::Error::SurrogateChar { unit })
+      }
+      value => Err(Error::WrongJValueType("char", value.type_name()).into()),
+    }
+  }
+}
+
+impl<'a> FromConstant<'a> for String {
+  const TYPE: Type<'static> = Type::Object("java/lang/String");
+
+  fn from_constant(env: &'a Env<'a>, value: ReturnValue<'a>) -> Result<Self> {
+    match value {
+      ReturnValue::String(value) => {
+        let string = value.to_string();
+        // The reference is not needed once the string is converted
+        env.delete_local_ref(value.into_string())?;
+
+        string
+      }
+      // Null strings are returned as objects
+      ReturnValue::Object(value) if value.is_null() => {
+        Err(Error::NullDeref("String constant").into())
+      }
+      value => Err(Error::WrongJValueType("string", value.type_name()).into()),
+    }
+  }
+}
@@ -0,0 +1,73 @@
+use crate::{env::Env, object::Object, Result, Type};
+use jni::{errors::Error, objects::JObject};
+
+/// A trait for Rust enums mirroring a Java enum class
+///
+/// This is usually implemented with `#[derive(JavaEnum)]` from `jnat_macros`
+pub trait JavaEnum: Sized {
+  /// The qualified name of the Java enum class
+  const CLASS: &'static str;
+
+  /// Gets the variant matching the name of a Java enum constant
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The name of the constant
+  fn from_name(name: &str) -> Option<Self>;
+
+  /// Gets the name of the Java enum constant matching the variant
+  fn name(&self) -> &'static str;
+
+  /// Converts a Java enum constant into the matching variant
+  ///
+  /// Returns `Error::WrongClass` if the object is not a constant of the enum class, and
+  /// `Error::FieldNotFound` if the constant has no matching variant
+  ///
+  /// # Arguments
+  ///
+  /// * `object` - The Java enum constant
+  fn from_java(object: &Object) -> Result<Self> {
+    object.check_instance_of(Self::CLASS)?;
+
+    let name = object.enum_name()?;
+
+    let variant = Self::from_name(&name).ok_or_else(|| Error::FieldNotFound {
+      name,
+      sig: Type::Object(Self::CLASS).into(),
+    })?;
+
+    Ok(variant)
+  }
+
+  /// Converts the variant into the matching Java enum constant
+  ///
+  /// Returns `Error::Exception` if the Java enum class cannot be found, and
+  /// `Error::FieldNotFound` if it has no matching constant
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  fn to_java<'a>(&self, env: &'a Env<'a>) -> Result<JObject<'a>> {
+    let r#type: String = Type::Object(Self::CLASS).into();
+
+    let mut jni_env = env.get_jni_env();
+    // The class is found first, so that only a missing constant is reported as FieldNotFound
+    let class = env.catch(jni_env.find_class(Self::CLASS))?;
+    let class = jni_env.auto_local(class);
+
+    match jni_env.get_static_field(&class, self.name(), &r#type) {
+      // The lookup leaves a NoSuchFieldError pending
+      Err(Error::JavaException) => {
+        jni_env.exception_clear()?;
+        Err(
+          Error::FieldNotFound {
+            name: self.name().into(),
+            sig: r#type,
+          }
+          .into(),
+        )
+      }
+      result => Ok(result?.l()?),
+    }
+  }
+}
@@ -1,10 +1,22 @@
 use crate::{
-  class::Class, object::Object, value::Value, Array, BooleanArray, ByteArray, CharArray,
-  DoubleArray, FloatArray, IntArray, LongArray, ObjectArray, ShortArray,
+  array::checked_length,
+  class::Class,
+  exception::Exception,
+  local_ref,
+  name::binary_name,
+  object::Object,
+  reflection,
+  string::{JavaString, StringChars, StringCritical},
+  throwable::ThrowableBuilder,
+  trace::operation,
+  value::Value,
+  version::Version,
+  vm, Array, BooleanArray, ByteArray, CharArray, DoubleArray, FloatArray, IntArray, LongArray,
+  ObjectArray, ShortArray, Type,
 };
 use jni::{
   errors::Error,
-  objects::{JObject, JString, JValueGen},
+  objects::{JObject, JString, JThrowable, JValueGen},
   sys::{jboolean, jchar},
   JNIEnv,
 };
@@ -27,7 +39,7 @@ impl<'a> Env<'a> {
 
   /// Gets the native interface
   pub fn get_jni_env(&self) -> JNIEnv<'_> {
-    unsafe { JNIEnv::unsafe_clone(&self.jni_env) }
+    unsafe { JNIEnv::unsafe_clone(self.jni_env) }
   }
 
   /// Gets a class, given a qualified name
@@ -35,9 +47,250 @@ impl<'a> Env<'a> {
   /// # Arguments
   ///
   /// * `name` - The qualified name of the class
-  pub fn get_class(&'a self, name: &str) -> Result<Class<'a>, Error> {
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn get_class(&'a self, name: &str) -> crate::Result<Class<'a>> {
     let mut jni_env = self.get_jni_env();
-    Ok(Class::new(self, jni_env.find_class(name)?))
+    let operation = operation!("find_class", class = Some(name.replace('/', ".")));
+    let result = self.catch(jni_env.find_class(name));
+    operation.finish(&result);
+
+    let class = result?;
+    local_ref::track(&class, "Env::get_class");
+
+    Ok(Class::new(self, class))
+  }
+
+  /// Gets a nested class, given a qualified name such as `com/example/Outer.Inner`
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The qualified name of the class, with nested classes separated by `.`
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn get_nested_class(&'a self, name: &str) -> crate::Result<Class<'a>> {
+    self.get_class(&binary_name(name))
+  }
+
+  /// Gets the class of a type, including primitive and array types
+  ///
+  /// # Arguments
+  ///
+  /// * `type` - The type
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn class_for_type(&'a self, r#type: Type) -> crate::Result<Class<'a>> {
+    let mut jni_env = self.get_jni_env();
+    let class = self.catch(reflection::class_for_type(&mut jni_env, r#type))?;
+    local_ref::track(&class, "Env::class_for_type");
+
+    Ok(Class::new(self, class))
+  }
+
+  /// Converts a pending Java exception into `Error::Exception`, clearing it
+  ///
+  /// # Arguments
+  ///
+  /// * `result` - The result of a JNI call
+  pub fn catch<T>(&self, result: Result<T, Error>) -> crate::Result<T> {
+    match result {
+      Err(Error::JavaException) => {
+        let mut jni_env = self.get_jni_env();
+
+        match Exception::take(&mut jni_env)? {
+          Some(exception) => Err(crate::Error::Exception(exception)),
+          None => Err(Error::JavaException.into()),
+        }
+      }
+      result => Ok(result?),
+    }
+  }
+
+  /// Converts the null result of a raw JNI function, returning the exception it left pending as
+  /// `Error::Exception`, or a `NullPtr` error if there is none
+  ///
+  /// # Arguments
+  ///
+  /// * `function` - The name of the JNI function, e.g. `GetModule`
+  pub(crate) fn catch_null<T>(&self, function: &'static str) -> crate::Result<T> {
+    if self.get_jni_env().exception_check()? {
+      self.catch(Err(Error::JavaException))
+    } else {
+      Err(Error::NullPtr(function).into())
+    }
+  }
+
+  /// Converts a pending Java exception like `catch`, and adds the class and member that were
+  /// accessed to other errors
+  ///
+  /// A failed lookup of the member leaves a `NoSuchMethodError` or `NoSuchFieldError` pending,
+  /// which is returned as `Error::Call` with a `MethodNotFound` or `FieldNotFound` source. The
+  /// context is only looked up if the call failed.
+  ///
+  /// # Arguments
+  ///
+  /// * `class` - Gets the qualified name of the class
+  /// * `member` - Formats the member in Java syntax, given the name of the class
+  /// * `lookup` - The name and descriptor the member was looked up with
+  /// * `result` - The result of a JNI call
+  pub(crate) fn catch_member<T>(
+    &self,
+    class: impl FnOnce(&mut JNIEnv) -> Result<String, Error>,
+    member: impl FnOnce(&str) -> String,
+    (name, descriptor): (&str, &str),
+    result: Result<T, Error>,
+  ) -> crate::Result<T> {
+    let source = match self.catch(result) {
+      Err(crate::Error::Jni(source)) => source,
+      // The lookup throws with the name of the member as the message
+      Err(crate::Error::Exception(exception))
+        if exception.message.as_deref() == Some(name)
+          && exception.class == "java.lang.NoSuchMethodError" =>
+      {
+        Error::MethodNotFound {
+          name: name.into(),
+          sig: descriptor.into(),
+        }
+      }
+      Err(crate::Error::Exception(exception))
+        if exception.message.as_deref() == Some(name)
+          && exception.class == "java.lang.NoSuchFieldError" =>
+      {
+        Error::FieldNotFound {
+          name: name.into(),
+          sig: descriptor.into(),
+        }
+      }
+      result => return result,
+    };
+
+    let class = self.context_class(class);
+
+    Err(crate::Error::Call {
+      member: member(&class),
+      class,
+      source,
+    })
+  }
+
+  /// Converts the result of a check into `Error::Check`, adding the class and member that were
+  /// accessed
+  ///
+  /// # Arguments
+  ///
+  /// * `class` - Gets the qualified name of the class
+  /// * `member` - Formats the member in Java syntax, given the name of the class
+  /// * `check` - The reason the use is invalid, if it is
+  pub(crate) fn check_member(
+    &self,
+    class: impl FnOnce(&mut JNIEnv) -> Result<String, Error>,
+    member: impl FnOnce(&str) -> String,
+    check: Result<Option<String>, Error>,
+  ) -> crate::Result<()> {
+    match check? {
+      Some(message) => {
+        let class = self.context_class(class);
+
+        Err(crate::Error::Check {
+          member: member(&class),
+          class,
+          message,
+        })
+      }
+      None => Ok(()),
+    }
+  }
+
+  /// Gets the name of a class for the context of an error
+  fn context_class(&self, class: impl FnOnce(&mut JNIEnv) -> Result<String, Error>) -> String {
+    let mut jni_env = self.get_jni_env();
+
+    // A pending exception is set aside during the lookup, and thrown again after it
+    let pending = match jni_env.exception_occurred() {
+      Ok(throwable) if !throwable.is_null() => {
+        let _ = jni_env.exception_clear();
+        Some(throwable)
+      }
+      _ => None,
+    };
+
+    let class = class(&mut jni_env).unwrap_or_else(|_| {
+      // The error is about the call, not about looking up its context
+      let _ = jni_env.exception_clear();
+      "<unknown class>".into()
+    });
+
+    if let Some(throwable) = pending {
+      let _ = jni_env.throw(throwable);
+    }
+
+    class
+  }
+
+  /// Checks that an object is an instance of a class, respecting the class hierarchy
+  ///
+  /// Returns `Error::WrongClass` if it is not, or if it is null
+  ///
+  /// # Arguments
+  ///
+  /// * `object` - The object
+  /// * `class` - The qualified name of the class, e.g. `java/lang/Throwable`
+  pub(crate) fn check_instance_of(&self, object: &JObject, class: &str) -> crate::Result<()> {
+    let mut jni_env = self.get_jni_env();
+
+    // IsInstanceOf is true for null, which is an instance of no class here
+    if !object.is_null() && self.catch(jni_env.is_instance_of(object, class))? {
+      return Ok(());
+    }
+
+    let actual = if object.is_null() {
+      "null".into()
+    } else {
+      let actual = self.catch(jni_env.get_object_class(object))?;
+      let actual = jni_env.auto_local(actual);
+      self.catch(reflection::get_name(&mut jni_env, &actual))?
+    };
+
+    Err(crate::Error::WrongClass {
+      expected: class.replace('/', "."),
+      actual,
+    })
+  }
+
+  /// Throws a new Java exception
+  ///
+  /// The exception is thrown once the native method returns, so the caller should return soon
+  /// after without making other JNI calls
+  ///
+  /// # Arguments
+  ///
+  /// * `class` - The qualified name of the exception's class, e.g. `java/io/IOException`
+  /// * `message` - The message of the exception
+  pub fn throw_new(&self, class: &str, message: &str) -> crate::Result<()> {
+    let mut jni_env = self.get_jni_env();
+    self.catch(jni_env.throw_new(class, message))
+  }
+
+  /// Throws an existing Java throwable
+  ///
+  /// Returns `Error::WrongClass` if the object is not a `java.lang.Throwable`
+  ///
+  /// # Arguments
+  ///
+  /// * `throwable` - The throwable
+  pub fn throw(&self, throwable: &Object) -> crate::Result<()> {
+    self.check_instance_of(throwable.get_object(), "java/lang/Throwable")?;
+
+    let mut jni_env = self.get_jni_env();
+    let throwable: &JThrowable = throwable.get_object().into();
+
+    Ok(jni_env.throw(throwable)?)
+  }
+
+  /// Creates a builder for a Java throwable, which can set a cause
+  ///
+  /// # Arguments
+  ///
+  /// * `class` - The qualified name of the throwable's class, e.g. `java/io/IOException`
+  pub fn throwable(&'a self, class: &'a str) -> ThrowableBuilder<'a> {
+    ThrowableBuilder::new(self, class)
   }
 
   // We won't be using this for consistency reasons
@@ -55,18 +308,176 @@ impl<'a> Env<'a> {
   /// # Arguments
   ///
   /// * `string` - The string to convert
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn new_string(&'a self, string: &'a str) -> Result<JObject<'a>, Error> {
-    Ok(JObject::from(self.jni_env.new_string(string)?))
+    let operation = operation!("new_string", class = Some(String::from("java.lang.String")));
+    let result = self.jni_env.new_string(string);
+    operation.finish(&result);
+
+    let string = JObject::from(result?);
+    local_ref::track(&string, "Env::new_string");
+
+    Ok(string)
   }
 
   /// Gets a string from the JVM, given a JString
   ///
+  /// Unpaired surrogates are replaced with U+FFFD, as with `StringChars::to_string_lossy`
+  ///
   /// # Arguments
   ///
   /// * `string` - The JString to convert
   pub fn get_string(&'a self, string: JString<'a>) -> Result<String, Error> {
-    let mut jni_env = self.get_jni_env();
-    Ok(jni_env.get_string(&string)?.into())
+    self.get_string_lossy(&string)
+  }
+
+  /// Creates a Java string, wrapped in a JavaString
+  ///
+  /// # Arguments
+  ///
+  /// * `string` - The string to convert
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn new_java_string(&'a self, string: &str) -> crate::Result<JavaString<'a>> {
+    let operation = operation!("new_string", class = Some(String::from("java.lang.String")));
+    let result = self.catch(self.get_jni_env().new_string(string));
+    operation.finish(&result);
+
+    let string = result?;
+    local_ref::track(&string, "Env::new_java_string");
+
+    Ok(JavaString::new(self, string))
+  }
+
+  /// Gets a string from the JVM, given a JString
+  ///
+  /// Unlike `get_string`, which replaces them, returns `Error::UnpairedSurrogate` if the string
+  /// has unpaired surrogates
+  ///
+  /// # Arguments
+  ///
+  /// * `string` - The JString to convert
+  pub fn get_string_strict(&self, string: &JString) -> crate::Result<String> {
+    let operation = operation!("get_string", class = Some(String::from("java.lang.String")));
+    let result = match self.get_ascii_string(string) {
+      Some(string) => Ok(string),
+      None => self
+        .get_string_chars(string)
+        .and_then(|chars| chars.to_string_strict()),
+    };
+    operation.finish(&result);
+
+    result
+  }
+
+  /// Borrows the UTF-16 chars of a string, without converting them
+  ///
+  /// # Arguments
+  ///
+  /// * `string` - The JString to borrow
+  pub fn get_string_chars<'s>(&self, string: &'s JString<'s>) -> crate::Result<StringChars<'s>> {
+    let operation = operation!(
+      "get_string_chars",
+      class = Some(String::from("java.lang.String"))
+    );
+    let result = StringChars::new(self, string);
+    operation.finish(&result);
+
+    result
+  }
+
+  /// Borrows the UTF-16 chars of a string in a critical region, where no JNI function may be
+  /// called until the chars are dropped
+  ///
+  /// # Arguments
+  ///
+  /// * `string` - The JString to borrow
+  pub fn get_string_critical<'s>(
+    &self,
+    string: &'s JString<'s>,
+  ) -> crate::Result<StringCritical<'s>> {
+    let operation = operation!(
+      "get_string_critical",
+      class = Some(String::from("java.lang.String"))
+    );
+    let result = StringCritical::new(self, string);
+    operation.finish(&result);
+
+    result
+  }
+
+  /// Creates a string from UTF-16 chars
+  ///
+  /// # Arguments
+  ///
+  /// * `chars` - The UTF-16 chars, which may have unpaired surrogates
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn new_string_utf16(&'a self, chars: &[u16]) -> crate::Result<JObject<'a>> {
+    let length = checked_length(chars.len())?;
+
+    let operation = operation!("new_string", class = Some(String::from("java.lang.String")));
+    let new_string = unsafe { (**self.raw_env()).v1_1.NewString };
+    let string = unsafe { new_string(self.raw_env(), chars.as_ptr(), length) };
+
+    let result = if string.is_null() {
+      self.catch(Err(Error::NullPtr("NewString")))
+    } else {
+      Ok(unsafe { JObject::from_raw(string as jni::sys::jobject) })
+    };
+    operation.finish(&result);
+
+    let string = result?;
+    local_ref::track(&string, "Env::new_string_utf16");
+
+    Ok(string)
+  }
+
+  /// Gets a string, replacing unpaired surrogates
+  pub(crate) fn get_string_lossy(&self, string: &JString) -> Result<String, Error> {
+    let operation = operation!("get_string", class = Some(String::from("java.lang.String")));
+    let jni_env = self.get_jni_env();
+
+    // Decoding the UTF-16 chars replaces unpaired surrogates like StringChars::to_string_lossy
+    let result = match self.get_ascii_string(string) {
+      Some(string) => Ok(string),
+      None if string.is_null() => Err(Error::NullPtr("string")),
+      None => match StringChars::borrow(self, string) {
+        Some(chars) => Ok(chars.to_string_lossy()),
+        None => match jni_env.exception_check() {
+          Ok(true) => Err(Error::JavaException),
+          Ok(false) => Err(Error::NullPtr("GetStringChars")),
+          Err(error) => Err(error),
+        },
+      },
+    };
+    operation.finish(&result);
+
+    result
+  }
+
+  /// Gets a string without decoding it, if it is pure ASCII
+  fn get_ascii_string(&self, string: &JString) -> Option<String> {
+    if string.is_null() {
+      return None;
+    }
+
+    let raw_env = self.raw_env();
+    let string = string.as_raw() as jni_sys::jstring;
+    let functions = unsafe { (**raw_env).v1_2 };
+
+    // Modified UTF-8 only has one byte per char if every char is ASCII, except NUL
+    let length = unsafe { (functions.GetStringLength)(raw_env, string) };
+    if length != unsafe { (functions.GetStringUTFLength)(raw_env, string) } {
+      return None;
+    }
+
+    // The extra byte is defensive, in case the JVM writes a NUL terminator after the region
+    let mut bytes = vec![0u8; length as usize + 1];
+    unsafe {
+      (functions.GetStringUTFRegion)(raw_env, string, 0, length, bytes.as_mut_ptr().cast())
+    };
+    bytes.truncate(length as usize);
+
+    String::from_utf8(bytes).ok()
   }
 
   /// Gets a JValueGen<JObject>, given a Value
@@ -86,6 +497,7 @@ impl<'a> Env<'a> {
       Value::Double(d) => JValueGen::Double(d),
       Value::Void => JValueGen::Void,
       Value::Object(object) => JValueGen::Object(object.get_object()),
+      Value::String(string) => JValueGen::Object(string.get_string()),
     }
   }
 
@@ -94,7 +506,7 @@ impl<'a> Env<'a> {
   /// # Arguments
   ///
   /// * `object` - The JValueGen<JObject> to convert
-  pub fn get_value(&self, jvaluegen: JValueGen<&'a JObject<'a>>) -> Value {
+  pub fn get_value(&self, jvaluegen: JValueGen<&'a JObject<'a>>) -> Value<'_> {
     match jvaluegen {
       JValueGen::Bool(b) => Value::Boolean(b != 0),
       JValueGen::Byte(b) => Value::Byte(b),
@@ -109,11 +521,123 @@ impl<'a> Env<'a> {
     }
   }
 
+  /// Deletes a local reference, such as one returned by a jnat function
+  ///
+  /// Local references are freed when a native method returns, but a native loop creating many of
+  /// them can exhaust the local reference table. With the `leak-check` feature, the reference is
+  /// no longer reported by `LocalRefScope`.
+  ///
+  /// # Arguments
+  ///
+  /// * `object` - The reference, e.g. a JObject, JClass or the array of `Array::get_array`
+  pub fn delete_local_ref<'o, O>(&self, object: O) -> crate::Result<()>
+  where
+    O: Into<JObject<'o>>,
+  {
+    let object = object.into();
+    local_ref::untrack(&object);
+
+    Ok(self.jni_env.delete_local_ref(object)?)
+  }
+
+  /// Creates a guard checking that the local references created by jnat while it lives are
+  /// deleted
+  #[cfg(feature = "leak-check")]
+  pub fn local_ref_scope(&self) -> crate::LocalRefScope {
+    crate::LocalRefScope::new()
+  }
+
+  /// Gets the JNI version of the JVM
+  pub fn version(&self) -> Version {
+    let get_version = unsafe { (**self.raw_env()).v1_1.GetVersion };
+    Version::from_raw(unsafe { get_version(self.raw_env()) })
+  }
+
+  /// Gets the module of a class, as a `java.lang.Module`
+  ///
+  /// Returns `Error::Unsupported` if the JVM is older than JNI 9
+  ///
+  /// # Arguments
+  ///
+  /// * `class` - The class
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn get_module(&self, class: &Class) -> crate::Result<JObject<'a>> {
+    self.require("GetModule", Version::V9)?;
+
+    let get_module = unsafe { (**self.raw_env()).v9.GetModule };
+    let module = unsafe { get_module(self.raw_env(), class.as_class().as_raw()) };
+
+    if module.is_null() {
+      return self.catch_null("GetModule");
+    }
+
+    let module = unsafe { JObject::from_raw(module) };
+    local_ref::track(&module, "Env::get_module");
+
+    Ok(module)
+  }
+
+  /// Gets whether an object is a virtual thread
+  ///
+  /// Returns `Error::Unsupported` if the JVM is older than JNI 21
+  ///
+  /// # Arguments
+  ///
+  /// * `object` - The object, which may be any object
+  pub fn is_virtual_thread(&self, object: &Object) -> crate::Result<bool> {
+    self.require("IsVirtualThread", Version::V21)?;
+
+    let is_virtual_thread = unsafe { (**self.raw_env()).v19.IsVirtualThread };
+    Ok(unsafe { is_virtual_thread(self.raw_env(), object.get_object().as_raw()) })
+  }
+
+  /// Returns `Error::Unsupported` if the JVM is older than the version that added a function
+  ///
+  /// # Arguments
+  ///
+  /// * `function` - The name of the function, e.g. `GetModule`
+  /// * `required` - The JNI version that added it
+  pub fn require(&self, function: &'static str, required: Version) -> crate::Result<()> {
+    let version = self.version();
+
+    if version < required {
+      return Err(crate::Error::Unsupported {
+        function,
+        required,
+        version,
+      });
+    }
+
+    Ok(())
+  }
+
+  /// Gets the raw environment, with the function table of JNI versions jni 0.21 does not know
+  pub(crate) fn raw_env(&self) -> *mut jni_sys::JNIEnv {
+    self.jni_env.get_raw() as *mut jni_sys::JNIEnv
+  }
+
   /// Creates a new boolean array
   ///
   /// # Arguments
   ///
   /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn try_new_boolean_array(&'a self, length: usize) -> crate::Result<BooleanArray<'a>> {
+    BooleanArray::try_new(self, length)
+  }
+
+  /// Creates a new boolean array
+  ///
+  /// This is a convenience for `try_new_boolean_array`
+  ///
+  /// # Panics
+  ///
+  /// Panics if the array cannot be created
+  ///
+  /// # Arguments
+  ///
+  /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn new_boolean_array(&'a self, length: usize) -> BooleanArray<'a> {
     BooleanArray::new(self, length)
   }
@@ -123,6 +647,23 @@ impl<'a> Env<'a> {
   /// # Arguments
   ///
   /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn try_new_byte_array(&'a self, length: usize) -> crate::Result<ByteArray<'a>> {
+    ByteArray::try_new(self, length)
+  }
+
+  /// Creates a new byte array
+  ///
+  /// This is a convenience for `try_new_byte_array`
+  ///
+  /// # Panics
+  ///
+  /// Panics if the array cannot be created
+  ///
+  /// # Arguments
+  ///
+  /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn new_byte_array(&'a self, length: usize) -> ByteArray<'a> {
     ByteArray::new(self, length)
   }
@@ -132,6 +673,23 @@ impl<'a> Env<'a> {
   /// # Arguments
   ///
   /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn try_new_char_array(&'a self, length: usize) -> crate::Result<CharArray<'a>> {
+    CharArray::try_new(self, length)
+  }
+
+  /// Creates a new char array
+  ///
+  /// This is a convenience for `try_new_char_array`
+  ///
+  /// # Panics
+  ///
+  /// Panics if the array cannot be created
+  ///
+  /// # Arguments
+  ///
+  /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn new_char_array(&'a self, length: usize) -> CharArray<'a> {
     CharArray::new(self, length)
   }
@@ -141,6 +699,23 @@ impl<'a> Env<'a> {
   /// # Arguments
   ///
   /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn try_new_double_array(&'a self, length: usize) -> crate::Result<DoubleArray<'a>> {
+    DoubleArray::try_new(self, length)
+  }
+
+  /// Creates a new double array
+  ///
+  /// This is a convenience for `try_new_double_array`
+  ///
+  /// # Panics
+  ///
+  /// Panics if the array cannot be created
+  ///
+  /// # Arguments
+  ///
+  /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn new_double_array(&'a self, length: usize) -> DoubleArray<'a> {
     DoubleArray::new(self, length)
   }
@@ -150,6 +725,23 @@ impl<'a> Env<'a> {
   /// # Arguments
   ///
   /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn try_new_float_array(&'a self, length: usize) -> crate::Result<FloatArray<'a>> {
+    FloatArray::try_new(self, length)
+  }
+
+  /// Creates a new float array
+  ///
+  /// This is a convenience for `try_new_float_array`
+  ///
+  /// # Panics
+  ///
+  /// Panics if the array cannot be created
+  ///
+  /// # Arguments
+  ///
+  /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn new_float_array(&'a self, length: usize) -> FloatArray<'a> {
     FloatArray::new(self, length)
   }
@@ -159,6 +751,23 @@ impl<'a> Env<'a> {
   /// # Arguments
   ///
   /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn try_new_int_array(&'a self, length: usize) -> crate::Result<IntArray<'a>> {
+    IntArray::try_new(self, length)
+  }
+
+  /// Creates a new int array
+  ///
+  /// This is a convenience for `try_new_int_array`
+  ///
+  /// # Panics
+  ///
+  /// Panics if the array cannot be created
+  ///
+  /// # Arguments
+  ///
+  /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn new_int_array(&'a self, length: usize) -> IntArray<'a> {
     IntArray::new(self, length)
   }
@@ -168,6 +777,23 @@ impl<'a> Env<'a> {
   /// # Arguments
   ///
   /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn try_new_long_array(&'a self, length: usize) -> crate::Result<LongArray<'a>> {
+    LongArray::try_new(self, length)
+  }
+
+  /// Creates a new long array
+  ///
+  /// This is a convenience for `try_new_long_array`
+  ///
+  /// # Panics
+  ///
+  /// Panics if the array cannot be created
+  ///
+  /// # Arguments
+  ///
+  /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn new_long_array(&'a self, length: usize) -> LongArray<'a> {
     LongArray::new(self, length)
   }
@@ -177,6 +803,23 @@ impl<'a> Env<'a> {
   /// # Arguments
   ///
   /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn try_new_short_array(&'a self, length: usize) -> crate::Result<ShortArray<'a>> {
+    ShortArray::try_new(self, length)
+  }
+
+  /// Creates a new short array
+  ///
+  /// This is a convenience for `try_new_short_array`
+  ///
+  /// # Panics
+  ///
+  /// Panics if the array cannot be created
+  ///
+  /// # Arguments
+  ///
+  /// * `length` - The length of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn new_short_array(&'a self, length: usize) -> ShortArray<'a> {
     ShortArray::new(self, length)
   }
@@ -187,7 +830,40 @@ impl<'a> Env<'a> {
   ///
   /// * `length` - The length of the array
   /// * `class` - The class of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn try_new_object_array(
+    &'a self,
+    length: usize,
+    class: &str,
+  ) -> crate::Result<ObjectArray<'a>> {
+    ObjectArray::try_new_with(self, length, class)
+  }
+
+  /// Creates a new object array
+  ///
+  /// This is a convenience for `try_new_object_array`
+  ///
+  /// # Panics
+  ///
+  /// Panics if the array cannot be created
+  ///
+  /// # Arguments
+  ///
+  /// * `length` - The length of the array
+  /// * `class` - The class of the array
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn new_object_array(&'a self, length: usize, class: &str) -> ObjectArray<'a> {
     ObjectArray::new_with(self, length, class)
   }
 }
+
+impl Env<'static> {
+  /// Gets the environment of the current thread, attaching it to the stored JVM if needed
+  ///
+  /// The thread stays attached until it exits, and is then detached. This works on Java threads
+  /// and Rust threads alike, but needs a JVM stored with `JavaVm::store`, or `Error::NoJavaVm` is
+  /// returned. The environment must not be used by thread-local destructors.
+  pub fn current() -> crate::Result<Env<'static>> {
+    vm::current_env()
+  }
+}
@@ -0,0 +1,167 @@
+use crate::{exception::Exception, version::Version};
+use std::fmt::{self, Display, Formatter};
+
+/// An error returned by jnat
+#[derive(Debug)]
+pub enum Error {
+  /// An error returned by the jni crate
+  Jni(jni::errors::Error),
+  /// An error returned by the jni crate, while accessing a member of a class
+  Call {
+    /// The qualified name of the class, e.g. `java.lang.String`
+    class: String,
+    /// The member in Java syntax, e.g. `int indexOf(java.lang.String)`
+    member: String,
+    /// The error returned by the jni crate
+    source: jni::errors::Error,
+  },
+  /// A member was used with invalid arguments, which was caught before reaching the JVM
+  ///
+  /// Some arguments are always validated, e.g. those of `Object::invoke_private`, while the checks
+  /// of the `checked` feature also validate the other calls
+  Check {
+    /// The qualified name of the class, e.g. `java.lang.String`
+    class: String,
+    /// The member in Java syntax, e.g. `int indexOf(java.lang.String)`
+    member: String,
+    /// Why the use is incorrect
+    message: String,
+  },
+  /// An object is not an instance of the class it was expected to be
+  WrongClass {
+    /// The qualified name of the expected class, e.g. `java.lang.Throwable`
+    expected: String,
+    /// The qualified name of the class of the object, or `null`
+    actual: String,
+  },
+  /// A Java exception was thrown, and has been cleared
+  Exception(Exception),
+  /// An array index is not in the array
+  IndexOutOfBounds {
+    /// The index
+    index: usize,
+    /// The length of the array
+    length: usize,
+  },
+  /// A length is larger than `i32::MAX`, the largest length of a Java array or string
+  LengthTooLarge {
+    /// The length
+    length: usize,
+  },
+  /// A Java char is a surrogate, which has no matching Rust char
+  SurrogateChar {
+    /// The surrogate
+    unit: u16,
+  },
+  /// A Java string is not valid UTF-16, as it has a surrogate that is not part of a pair
+  UnpairedSurrogate {
+    /// The index of the surrogate, in UTF-16 code units
+    index: usize,
+    /// The surrogate
+    unit: u16,
+  },
+  /// A JVM could not be created through the invocation API
+  Invocation(String),
+  /// A JNI function is not available, because the JVM is older than the version that added it
+  Unsupported {
+    /// The name of the JNI function, e.g. `GetModule`
+    function: &'static str,
+    /// The JNI version that added the function
+    required: Version,
+    /// The JNI version of the JVM
+    version: Version,
+  },
+  /// No JVM was stored with `JavaVm::store`, so the current thread cannot be attached
+  NoJavaVm,
+  /// A member could not be made accessible, usually because its module does not open it
+  Inaccessible {
+    /// The qualified name of the class declaring the member
+    class: String,
+    /// The name of the member
+    member: String,
+    /// The message of the exception thrown by `setAccessible`
+    message: String,
+  },
+  /// A direct buffer is read-only, so its memory cannot be borrowed mutably
+  ReadOnlyBuffer,
+}
+
+/// A result with a jnat error
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl From<jni::errors::Error> for Error {
+  fn from(error: jni::errors::Error) -> Self {
+    Error::Jni(error)
+  }
+}
+
+impl Display for Error {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::Jni(error) => error.fmt(f),
+      Error::Call {
+        class,
+        member,
+        source,
+      } => write!(f, "{} in {}: {}", member, class, source),
+      Error::Check {
+        class,
+        member,
+        message,
+      } => write!(f, "Invalid use of {} in {}: {}", member, class, message),
+      Error::WrongClass { expected, actual } => {
+        write!(f, "Expected an instance of {}, got {}", expected, actual)
+      }
+      Error::Exception(exception) => exception.fmt(f),
+      Error::IndexOutOfBounds { index, length } => {
+        write!(f, "Index {} out of bounds for length {}", index, length)
+      }
+      Error::LengthTooLarge { length } => {
+        write!(f, "Length {} is larger than {}", length, i32::MAX)
+      }
+      Error::SurrogateChar { unit } => write!(f, "Char {:#06x} is a surrogate", unit),
+      Error::UnpairedSurrogate { index, unit } => {
+        write!(f, "Unpaired surrogate {:#06x} at index {}", unit, index)
+      }
+      Error::Invocation(message) => write!(f, "Cannot create JVM: {}", message),
+      Error::Unsupported {
+        function,
+        required,
+        version,
+      } => write!(
+        f,
+        "{} needs JNI {}, but the JVM supports JNI {}",
+        function, required, version
+      ),
+      Error::NoJavaVm => write!(f, "No JVM is stored, see JavaVm::store"),
+      Error::Inaccessible {
+        class,
+        member,
+        message,
+      } => write!(f, "Cannot access {}.{}: {}", class, member, message),
+      Error::ReadOnlyBuffer => write!(f, "The buffer is read-only"),
+    }
+  }
+}
+
+impl std::error::Error for Error {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Error::Jni(error) => Some(error),
+      Error::Call { source, .. } => Some(source),
+      // The exception is displayed as the error itself, so its cause is the source
+      Error::Exception(exception) => exception.source(),
+      Error::Check { .. }
+      | Error::WrongClass { .. }
+      | Error::IndexOutOfBounds { .. }
+      | Error::LengthTooLarge { .. }
+      | Error::SurrogateChar { .. }
+      | Error::UnpairedSurrogate { .. }
+      | Error::Invocation(_)
+      | Error::Unsupported { .. }
+      | Error::NoJavaVm
+      | Error::Inaccessible { .. }
+      | Error::ReadOnlyBuffer => None,
+    }
+  }
+}
@@ -0,0 +1,301 @@
+use crate::{env::Env, reflection::get_name};
+use jni::{
+  errors::Result,
+  objects::{GlobalRef, JObject, JObjectArray, JString, JThrowable},
+  JNIEnv,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// A Java exception, caught and cleared by jnat
+#[derive(Debug, Clone)]
+pub struct Exception {
+  /// The qualified name of the throwable's class, e.g. `java.io.IOException`
+  pub class: String,
+  /// The message of the throwable
+  pub message: Option<String>,
+  /// The cause of the throwable
+  pub cause: Option<Box<Exception>>,
+  /// The stack trace of the throwable
+  pub stack_trace: Vec<StackTraceElement>,
+  throwable: GlobalRef,
+}
+
+/// A frame of a Java stack trace
+#[derive(Debug, Clone)]
+pub struct StackTraceElement {
+  /// The qualified name of the class, e.g. `java.lang.String`
+  pub class: String,
+  /// The name of the method
+  pub method: String,
+  /// The name of the source file
+  pub file: Option<String>,
+  /// The line number, or a negative number if it is unknown
+  pub line: i32,
+}
+
+impl Exception {
+  /// Takes the pending exception, if there is one, and clears it
+  ///
+  /// If the exception cannot be converted, it is left pending and the error is returned
+  ///
+  /// # Arguments
+  ///
+  /// * `jni_env` - The JNI environment
+  pub(crate) fn take(jni_env: &mut JNIEnv) -> Result<Option<Exception>> {
+    if !jni_env.exception_check()? {
+      return Ok(None);
+    }
+
+    let throwable = jni_env.exception_occurred()?;
+    let throwable = jni_env.auto_local(throwable);
+    jni_env.exception_clear()?;
+
+    match Exception::from_throwable(jni_env, &throwable) {
+      Ok(exception) => Ok(Some(exception)),
+      Err(error) => {
+        // The throwable would be lost otherwise, so it is thrown again in place of any exception
+        // thrown while converting it
+        let _ = jni_env.exception_clear();
+        let _ = jni_env.throw(&*throwable);
+
+        Err(error)
+      }
+    }
+  }
+
+  /// Creates an Exception from a throwable and its causes
+  ///
+  /// # Arguments
+  ///
+  /// * `jni_env` - The JNI environment
+  /// * `throwable` - The throwable
+  pub(crate) fn from_throwable(jni_env: &mut JNIEnv, throwable: &JObject) -> Result<Exception> {
+    // Causes can form a cycle, which Java guards against with an identity set
+    let mut chain = vec![jni_env.new_local_ref(throwable)?];
+
+    loop {
+      let cause = jni_env
+        .call_method(
+          chain.last().unwrap(),
+          "getCause",
+          "()Ljava/lang/Throwable;",
+          &[],
+        )?
+        .l()?;
+
+      if cause.is_null() {
+        break;
+      }
+
+      let mut seen = false;
+      for throwable in &chain {
+        seen |= jni_env.is_same_object(throwable, &cause)?;
+      }
+
+      if seen {
+        break;
+      }
+
+      chain.push(cause);
+    }
+
+    let mut exception = None;
+
+    for throwable in chain.into_iter().rev() {
+      let throwable = jni_env.auto_local(throwable);
+
+      let class = jni_env.get_object_class(&throwable)?;
+      let class = jni_env.auto_local(class);
+
+      let message = jni_env
+        .call_method(&throwable, "getMessage", "()Ljava/lang/String;", &[])?
+        .l()?;
+
+      exception = Some(Box::new(Exception {
+        class: get_name(jni_env, &class)?,
+        message: get_optional_string(jni_env, message)?,
+        cause: exception,
+        stack_trace: get_stack_trace(jni_env, &throwable)?,
+        throwable: jni_env.new_global_ref(&throwable)?,
+      }));
+    }
+
+    Ok(*exception.expect("the chain holds at least the throwable"))
+  }
+
+  /// Throws the exception again
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  pub fn rethrow(&self, env: &Env) -> Result<()> {
+    let mut jni_env = env.get_jni_env();
+    let throwable = jni_env.new_local_ref(&self.throwable)?;
+
+    jni_env.throw(JThrowable::from(throwable))
+  }
+
+  /// Checks whether the throwable is an instance of a class, respecting the class hierarchy
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `class` - The qualified name of the class, e.g. `java/io/IOException`
+  pub fn is_instance_of(&self, env: &Env, class: &str) -> crate::Result<bool> {
+    let mut jni_env = env.get_jni_env();
+    env.catch(jni_env.is_instance_of(&self.throwable, class))
+  }
+
+  /// Adds another exception to the suppressed exceptions of the throwable, like
+  /// `Throwable.addSuppressed`
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `suppressed` - The exception to add
+  pub fn add_suppressed(&self, env: &Env, suppressed: &Exception) -> crate::Result<()> {
+    let mut jni_env = env.get_jni_env();
+    let result = jni_env.call_method(
+      &self.throwable,
+      "addSuppressed",
+      "(Ljava/lang/Throwable;)V",
+      &[suppressed.throwable.as_obj().into()],
+    );
+
+    env.catch(result)?;
+    Ok(())
+  }
+
+  /// Gets a global reference to the throwable
+  pub fn get_throwable(&self) -> &GlobalRef {
+    &self.throwable
+  }
+}
+
+impl Display for Exception {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match &self.message {
+      Some(message) => write!(f, "{}: {}", self.class, message),
+      None => write!(f, "{}", self.class),
+    }
+  }
+}
+
+impl std::error::Error for Exception {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    self
+      .cause
+      .as_deref()
+      .map(|c| c as &(dyn std::error::Error + 'static))
+  }
+}
+
+impl Display for StackTraceElement {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match (&self.file, self.line) {
+      (Some(file), line) if line >= 0 => {
+        write!(f, "{}.{}({}:{})", self.class, self.method, file, line)
+      }
+      (Some(file), _) => write!(f, "{}.{}({})", self.class, self.method, file),
+      (None, _) => write!(f, "{}.{}(Unknown Source)", self.class, self.method),
+    }
+  }
+}
+
+/// Converts a possibly null string
+fn get_optional_string(jni_env: &mut JNIEnv, string: JObject) -> Result<Option<String>> {
+  if string.is_null() {
+    return Ok(None);
+  }
+
+  let string = jni_env.auto_local(JString::from(string));
+  let string = jni_env.get_string(&string)?.into();
+
+  Ok(Some(string))
+}
+
+/// Gets the stack trace of a throwable
+fn get_stack_trace(jni_env: &mut JNIEnv, throwable: &JObject) -> Result<Vec<StackTraceElement>> {
+  let elements = jni_env
+    .call_method(
+      throwable,
+      "getStackTrace",
+      "()[Ljava/lang/StackTraceElement;",
+      &[],
+    )?
+    .l()?;
+  let elements = jni_env.auto_local(JObjectArray::from(elements));
+
+  let mut stack_trace = Vec::new();
+
+  for i in 0..jni_env.get_array_length(&*elements)? {
+    let element = jni_env.get_object_array_element(&*elements, i)?;
+    let element = jni_env.auto_local(element);
+
+    let class = jni_env
+      .call_method(&element, "getClassName", "()Ljava/lang/String;", &[])?
+      .l()?;
+    let method = jni_env
+      .call_method(&element, "getMethodName", "()Ljava/lang/String;", &[])?
+      .l()?;
+    let file = jni_env
+      .call_method(&element, "getFileName", "()Ljava/lang/String;", &[])?
+      .l()?;
+
+    stack_trace.push(StackTraceElement {
+      class: get_optional_string(jni_env, class)?.unwrap_or_default(),
+      method: get_optional_string(jni_env, method)?.unwrap_or_default(),
+      file: get_optional_string(jni_env, file)?,
+      line: jni_env
+        .call_method(&element, "getLineNumber", "()I", &[])?
+        .i()?,
+    });
+  }
+
+  Ok(stack_trace)
+}
+
+/// Matches the exception of a failed call against Java classes, in order
+///
+/// Each arm handles the exception if it is an instance of the class, respecting the class
+/// hierarchy. An exception that matches no arm is returned as `Error::Exception` and stays
+/// cleared, so returning it from a `jnat!` function with `?` throws it to Java. If matching
+/// fails, e.g. because a class cannot be found, the exception is returned with the failure added
+/// to its suppressed exceptions. Other errors and successful results are passed through.
+///
+/// ```ignore
+/// let value = catch!(&env, class.call_static_method("read", signature, &[]), {
+///   "java/io/FileNotFoundException" => |e| fallback,
+///   "java/io/IOException" => |e| retry(&env)?,
+/// })?;
+/// ```
+#[macro_export]
+macro_rules! catch {
+  ($env:expr, $result:expr, { $($class:literal => |$exception:pat_param| $handler:expr),+ $(,)? }) => {
+    match $result {
+      ::std::result::Result::Err($crate::Error::Exception(exception)) => 'catch: {
+        let env: &$crate::Env = $env;
+
+        $(
+          match exception.is_instance_of(env, $class) {
+            ::std::result::Result::Ok(true) => {
+              let $exception = exception;
+              break 'catch ::std::result::Result::Ok($handler);
+            }
+            ::std::result::Result::Ok(false) => {}
+            ::std::result::Result::Err(error) => {
+              if let $crate::Error::Exception(failure) = &error {
+                let _ = exception.add_suppressed(env, failure);
+              }
+
+              break 'catch ::std::result::Result::Err($crate::Error::Exception(exception));
+            }
+          }
+        )+
+
+        ::std::result::Result::Err($crate::Error::Exception(exception))
+      }
+      result => result,
+    }
+  };
+}
@@ -0,0 +1,221 @@
+use crate::{error::Error, version::Version, vm::JavaVm};
+use jni::{
+  sys::{self, jint, JavaVMInitArgs, JavaVMOption, JNI_FALSE, JNI_OK, JNI_TRUE},
+  JavaVM,
+};
+use libloading::Library;
+use std::{
+  env,
+  ffi::{c_void, CString},
+  path::{Path, PathBuf},
+  ptr,
+};
+
+/// The signature of `JNI_CreateJavaVM`
+type CreateJavaVm =
+  unsafe extern "system" fn(*mut *mut sys::JavaVM, *mut *mut c_void, *mut c_void) -> jint;
+
+/// A builder for a Java virtual machine
+pub struct JavaVmBuilder {
+  libjvm: Option<PathBuf>,
+  classpath: Vec<PathBuf>,
+  options: Vec<String>,
+  version: Version,
+  ignore_unrecognized: bool,
+}
+
+impl JavaVmBuilder {
+  /// Creates a new JavaVmBuilder, requesting JNI 1.8
+  pub fn new() -> JavaVmBuilder {
+    JavaVmBuilder {
+      libjvm: None,
+      classpath: Vec::new(),
+      options: Vec::new(),
+      version: Version::V1_8,
+      ignore_unrecognized: false,
+    }
+  }
+
+  /// Sets the path of the JVM library, e.g. `libjvm.so`
+  ///
+  /// By default, it is looked up in `JAVA_HOME`, then next to the `java` executable in `PATH`
+  ///
+  /// # Arguments
+  ///
+  /// * `path` - The path of the library
+  pub fn libjvm(mut self, path: impl Into<PathBuf>) -> JavaVmBuilder {
+    self.libjvm = Some(path.into());
+    self
+  }
+
+  /// Adds a directory or jar to the classpath
+  ///
+  /// # Arguments
+  ///
+  /// * `entry` - The directory or jar
+  pub fn classpath(mut self, entry: impl Into<PathBuf>) -> JavaVmBuilder {
+    self.classpath.push(entry.into());
+    self
+  }
+
+  /// Adds an option, e.g. `-Xmx512m` or `-Dkey=value`
+  ///
+  /// # Arguments
+  ///
+  /// * `option` - The option
+  pub fn option(mut self, option: impl Into<String>) -> JavaVmBuilder {
+    self.options.push(option.into());
+    self
+  }
+
+  /// Sets the JNI version to request
+  ///
+  /// # Arguments
+  ///
+  /// * `version` - The version
+  pub fn version(mut self, version: Version) -> JavaVmBuilder {
+    self.version = version;
+    self
+  }
+
+  /// Sets whether options the JVM does not recognize are ignored instead of failing
+  ///
+  /// # Arguments
+  ///
+  /// * `ignore` - Whether to ignore them
+  pub fn ignore_unrecognized(mut self, ignore: bool) -> JavaVmBuilder {
+    self.ignore_unrecognized = ignore;
+    self
+  }
+
+  /// Loads the JVM library and creates the JVM, attaching the current thread to it
+  ///
+  /// The JVM is destroyed when the returned JavaVm is dropped, unless it is stored with
+  /// `JavaVm::store` for `Env::current`
+  ///
+  /// Returns `Error::Invocation` if the library cannot be found or loaded, or if the JVM cannot
+  /// be created
+  pub fn build(self) -> crate::Result<JavaVm> {
+    let libjvm = match self.libjvm {
+      Some(libjvm) => libjvm,
+      None => find_libjvm()?,
+    };
+
+    let library = unsafe { Library::new(&libjvm) }
+      .map_err(|error| Error::Invocation(format!("cannot load {}: {}", libjvm.display(), error)))?;
+    let create_java_vm = unsafe { library.get::<CreateJavaVm>(b"JNI_CreateJavaVM\0") }
+      .map_err(|error| Error::Invocation(format!("cannot find JNI_CreateJavaVM: {}", error)))?;
+
+    let mut options = self.options;
+    if !self.classpath.is_empty() {
+      let classpath = env::join_paths(&self.classpath)
+        .map_err(|error| Error::Invocation(format!("invalid classpath: {}", error)))?;
+      options.push(format!("-Djava.class.path={}", classpath.to_string_lossy()));
+    }
+
+    let options = options
+      .into_iter()
+      .map(|option| {
+        CString::new(option).map_err(|_| Error::Invocation("option contains a nul byte".into()))
+      })
+      .collect::<crate::Result<Vec<CString>>>()?;
+    let mut raw_options = options
+      .iter()
+      .map(|option| JavaVMOption {
+        optionString: option.as_ptr() as *mut _,
+        extraInfo: ptr::null_mut(),
+      })
+      .collect::<Vec<JavaVMOption>>();
+
+    let mut args = JavaVMInitArgs {
+      version: self.version.to_raw(),
+      nOptions: raw_options.len() as jint,
+      options: raw_options.as_mut_ptr(),
+      ignoreUnrecognized: if self.ignore_unrecognized {
+        JNI_TRUE
+      } else {
+        JNI_FALSE
+      },
+    };
+
+    let mut vm = ptr::null_mut();
+    let mut jni_env = ptr::null_mut();
+    let result = unsafe {
+      create_java_vm(
+        &mut vm,
+        &mut jni_env,
+        &mut args as *mut JavaVMInitArgs as *mut c_void,
+      )
+    };
+
+    if result != JNI_OK {
+      return Err(Error::Invocation(format!(
+        "JNI_CreateJavaVM failed: {}",
+        describe_error_code(result)
+      )));
+    }
+
+    Ok(JavaVm::created(unsafe { JavaVM::from_raw(vm)? }, library))
+  }
+}
+
+impl Default for JavaVmBuilder {
+  fn default() -> Self {
+    JavaVmBuilder::new()
+  }
+}
+
+/// Describes an error code returned by the invocation API
+fn describe_error_code(code: jint) -> &'static str {
+  match code {
+    sys::JNI_EDETACHED => "thread detached from the VM",
+    sys::JNI_EVERSION => "JNI version error",
+    sys::JNI_ENOMEM => "not enough memory",
+    sys::JNI_EEXIST => "VM already created",
+    sys::JNI_EINVAL => "invalid arguments",
+    _ => "unknown error",
+  }
+}
+
+/// Finds the JVM library in `JAVA_HOME`, or next to the `java` executable in `PATH`
+fn find_libjvm() -> crate::Result<PathBuf> {
+  let java_home = env::var_os("JAVA_HOME").map(PathBuf::from).or_else(|| {
+    // java is usually a symlink into the Java home, e.g. /usr/bin/java
+    let path = env::var_os("PATH")?;
+    let java = env::split_paths(&path)
+      .map(|dir| dir.join(if cfg!(windows) { "java.exe" } else { "java" }))
+      .find(|java| java.is_file())?;
+
+    Some(java.canonicalize().ok()?.parent()?.parent()?.to_path_buf())
+  });
+
+  let java_home = java_home.ok_or_else(|| {
+    Error::Invocation("cannot find the JVM library, set JAVA_HOME or call libjvm".into())
+  })?;
+
+  find_libjvm_in(&java_home).ok_or_else(|| {
+    Error::Invocation(format!(
+      "cannot find the JVM library in {}",
+      java_home.display()
+    ))
+  })
+}
+
+/// Finds the JVM library in a Java home
+fn find_libjvm_in(java_home: &Path) -> Option<PathBuf> {
+  const CANDIDATES: &[&str] = &[
+    "lib/server/libjvm.so",
+    "lib/server/libjvm.dylib",
+    "bin/server/jvm.dll",
+    // Java 8 keeps the library inside the bundled JRE
+    "jre/lib/server/libjvm.so",
+    "jre/lib/amd64/server/libjvm.so",
+    "jre/lib/aarch64/server/libjvm.so",
+    "jre/bin/server/jvm.dll",
+  ];
+
+  CANDIDATES
+    .iter()
+    .map(|candidate| java_home.join(candidate))
+    .find(|candidate| candidate.is_file())
+}
@@ -1,18 +1,56 @@
 mod array;
+mod backtrace;
+mod buffer;
+mod check;
 mod class;
+mod constant;
+mod r#enum;
 mod env;
+mod error;
+mod exception;
+#[cfg(feature = "invocation")]
+mod invocation;
+mod local_ref;
+#[cfg(feature = "log")]
+mod logger;
+mod name;
 mod object;
+mod panic;
+mod reflection;
 mod signature;
+mod string;
+mod throwable;
+mod trace;
 mod r#type;
 mod value;
+mod version;
+mod vm;
 
 pub use array::*;
+pub use backtrace::*;
+pub use buffer::*;
 pub use class::*;
+pub use constant::*;
 pub use env::*;
+pub use error::*;
+pub use exception::*;
+#[cfg(feature = "invocation")]
+pub use invocation::*;
+#[cfg(feature = "leak-check")]
+pub use local_ref::*;
+#[cfg(feature = "log")]
+pub use logger::*;
+pub use name::*;
 pub use object::*;
+pub use panic::*;
+pub use r#enum::*;
 pub use r#type::*;
 pub use signature::*;
+pub use string::*;
+pub use throwable::*;
 pub use value::*;
+pub use version::*;
+pub use vm::*;
 
 #[cfg(test)]
 mod test;
@@ -22,3 +60,13 @@ pub use jni;
 
 #[cfg(feature = "jnat-macros")]
 pub use jnat_macros;
+
+#[cfg(feature = "jnat-macros")]
+pub use jnat_macros::{on_load, on_unload};
+
+#[doc(hidden)]
+pub mod __private {
+  pub use crate::local_ref::NativeFrame;
+  pub use crate::panic::catch_unwind;
+  pub use jni;
+}
@@ -0,0 +1,243 @@
+use jni::objects::{JObject, JValueGen};
+
+#[cfg(feature = "leak-check")]
+use std::{cell::RefCell, fmt, marker::PhantomData, panic::Location};
+
+#[cfg(feature = "leak-check")]
+thread_local! {
+  /// The local references created by jnat on this thread that were not deleted, oldest first
+  ///
+  /// Local references belong to the thread that created them, so they are tracked per thread
+  static LOCAL_REFS: RefCell<LocalRefs> = const {
+    RefCell::new(LocalRefs {
+      next: 0,
+      live: Vec::new(),
+    })
+  };
+}
+
+#[cfg(feature = "leak-check")]
+struct LocalRefs {
+  /// The id of the next local reference
+  next: u64,
+  live: Vec<LocalRef>,
+}
+
+/// A local reference created by jnat that was not deleted, with the call that created it
+#[cfg(feature = "leak-check")]
+#[derive(Debug, Clone)]
+pub struct LocalRef {
+  id: u64,
+  raw: jni::sys::jobject,
+  function: &'static str,
+  location: &'static Location<'static>,
+}
+
+#[cfg(feature = "leak-check")]
+impl LocalRef {
+  /// Gets the jnat function that created the reference, e.g. `Env::get_class`
+  pub fn function(&self) -> &'static str {
+    self.function
+  }
+
+  /// Gets the location of the call that created the reference
+  pub fn location(&self) -> &'static Location<'static> {
+    self.location
+  }
+}
+
+#[cfg(feature = "leak-check")]
+impl fmt::Display for LocalRef {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} at {}", self.function, self.location)
+  }
+}
+
+/// A guard checking that the local references created by jnat while it lives are deleted
+///
+/// References are counted as deleted once passed to `Env::delete_local_ref`, so references
+/// deleted through the jni crate or freed by popping a local frame are still reported. The guard
+/// panics when dropped if references created on its thread since it was created are left, unless
+/// the thread is already panicking.
+#[cfg(feature = "leak-check")]
+pub struct LocalRefScope {
+  start: u64,
+  // The references are tracked per thread, so the guard must stay on its thread
+  _thread: PhantomData<*const ()>,
+}
+
+#[cfg(feature = "leak-check")]
+impl LocalRefScope {
+  /// Creates a new LocalRefScope, starting from the references created so far
+  pub fn new() -> LocalRefScope {
+    LocalRefScope {
+      start: LOCAL_REFS.with(|refs| refs.borrow().next),
+      _thread: PhantomData,
+    }
+  }
+
+  /// Gets the number of references created in the scope that were not deleted
+  pub fn count(&self) -> usize {
+    LOCAL_REFS.with(|refs| {
+      let refs = refs.borrow();
+      refs.live.iter().filter(|r| r.id >= self.start).count()
+    })
+  }
+
+  /// Gets the references created in the scope that were not deleted, oldest first
+  pub fn leaked(&self) -> Vec<LocalRef> {
+    LOCAL_REFS.with(|refs| {
+      let refs = refs.borrow();
+      refs
+        .live
+        .iter()
+        .filter(|r| r.id >= self.start)
+        .cloned()
+        .collect()
+    })
+  }
+
+  /// Asserts that every reference created in the scope was deleted
+  ///
+  /// # Panics
+  ///
+  /// Panics with the calls that created the references that were not deleted
+  #[track_caller]
+  pub fn assert_balanced(&self) {
+    let leaked = self.leaked();
+
+    if !leaked.is_empty() {
+      let calls = leaked
+        .iter()
+        .map(|r| format!("\n  {}", r))
+        .collect::<String>();
+
+      panic!(
+        "{} local references were not deleted, created by:{}",
+        leaked.len(),
+        calls
+      );
+    }
+  }
+}
+
+#[cfg(feature = "leak-check")]
+impl Default for LocalRefScope {
+  fn default() -> LocalRefScope {
+    LocalRefScope::new()
+  }
+}
+
+#[cfg(feature = "leak-check")]
+impl Drop for LocalRefScope {
+  fn drop(&mut self) {
+    // Panicking again would abort, hiding the first panic
+    if !std::thread::panicking() {
+      self.assert_balanced();
+    }
+  }
+}
+
+/// A guard forgetting the local references created during a native method when it returns, as the
+/// JVM frees them then
+///
+/// This is used by the functions generated with `jnat!`, so that the tracked references do not
+/// grow with every call.
+#[doc(hidden)]
+pub struct NativeFrame {
+  #[cfg(feature = "leak-check")]
+  start: u64,
+}
+
+impl NativeFrame {
+  /// Starts a native method, from the references created so far
+  #[cfg_attr(not(feature = "leak-check"), inline(always))]
+  pub fn enter() -> NativeFrame {
+    NativeFrame {
+      #[cfg(feature = "leak-check")]
+      start: LOCAL_REFS.with(|refs| refs.borrow().next),
+    }
+  }
+}
+
+#[cfg(feature = "leak-check")]
+impl Drop for NativeFrame {
+  fn drop(&mut self) {
+    LOCAL_REFS.with(|refs| {
+      let mut refs = refs.borrow_mut();
+
+      // The references are ordered by id, as they are only ever pushed or removed
+      let kept = refs.live.partition_point(|r| r.id < self.start);
+      refs.live.truncate(kept);
+    });
+  }
+}
+
+/// Records a local reference created by a jnat function, unless it is null
+///
+/// # Arguments
+///
+/// * `object` - The reference
+/// * `function` - The jnat function that created it
+#[cfg(feature = "leak-check")]
+#[track_caller]
+pub(crate) fn track(object: &JObject, function: &'static str) {
+  if object.is_null() {
+    return;
+  }
+
+  let location = Location::caller();
+
+  LOCAL_REFS.with(|refs| {
+    let mut refs = refs.borrow_mut();
+    let id = refs.next;
+
+    refs.next += 1;
+    refs.live.push(LocalRef {
+      id,
+      raw: object.as_raw(),
+      function,
+      location,
+    });
+  });
+}
+
+#[cfg(not(feature = "leak-check"))]
+#[inline(always)]
+pub(crate) fn track(_: &JObject, _: &'static str) {}
+
+/// Records that a local reference was deleted
+///
+/// # Arguments
+///
+/// * `object` - The reference
+#[cfg(feature = "leak-check")]
+pub(crate) fn untrack(object: &JObject) {
+  let raw = object.as_raw();
+
+  LOCAL_REFS.with(|refs| {
+    let mut refs = refs.borrow_mut();
+
+    // A deleted handle can be reused by the JVM, so the newest reference is the one deleted
+    if let Some(position) = refs.live.iter().rposition(|r| r.raw == raw) {
+      refs.live.remove(position);
+    }
+  });
+}
+
+#[cfg(not(feature = "leak-check"))]
+#[inline(always)]
+pub(crate) fn untrack(_: &JObject) {}
+
+/// Records the local reference a jnat function returned in a value, if it returned an object
+///
+/// # Arguments
+///
+/// * `value` - The value
+/// * `function` - The jnat function that returned it
+#[cfg_attr(feature = "leak-check", track_caller)]
+pub(crate) fn track_value(value: &JValueGen<JObject>, function: &'static str) {
+  if let JValueGen::Object(object) = value {
+    track(object, function);
+  }
+}
@@ -0,0 +1,317 @@
+use crate::env::Env;
+use jni::{
+  objects::{GlobalRef, JObject, JString, JValueGen},
+  JNIEnv,
+};
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::{
+  collections::HashMap,
+  sync::{Mutex, PoisonError},
+};
+
+/// The Java logging API that `JavaLogger` forwards records to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogBackend {
+  /// `java.util.logging.Logger`
+  JavaUtilLogging,
+  /// `java.lang.System.Logger`, available since Java 9
+  SystemLogger,
+}
+
+impl LogBackend {
+  /// Gets the class of the levels of the backend
+  fn level_class(self) -> &'static str {
+    match self {
+      LogBackend::JavaUtilLogging => "java/util/logging/Level",
+      LogBackend::SystemLogger => "java/lang/System$Logger$Level",
+    }
+  }
+
+  /// Gets the name of the Java level a Rust level maps to
+  fn level_name(self, level: Level) -> &'static str {
+    match (self, level) {
+      (LogBackend::JavaUtilLogging, Level::Error) => "SEVERE",
+      (LogBackend::JavaUtilLogging, Level::Warn) => "WARNING",
+      (LogBackend::JavaUtilLogging, Level::Info) => "INFO",
+      (LogBackend::JavaUtilLogging, Level::Debug) => "FINE",
+      (LogBackend::JavaUtilLogging, Level::Trace) => "FINEST",
+      (LogBackend::SystemLogger, Level::Error) => "ERROR",
+      (LogBackend::SystemLogger, Level::Warn) => "WARNING",
+      (LogBackend::SystemLogger, Level::Info) => "INFO",
+      (LogBackend::SystemLogger, Level::Debug) => "DEBUG",
+      (LogBackend::SystemLogger, Level::Trace) => "TRACE",
+    }
+  }
+}
+
+/// A `log::Log` implementation forwarding records to Java logging
+///
+/// Records are logged to a Java logger named after the Rust module path, e.g. `my_crate.net` for
+/// `my_crate::net`. It works from any Rust thread, attaching it with `Env::current`, so the JVM
+/// must be stored with `JavaVm::store`. Records logged while an exception is pending, or before a
+/// JVM is stored, are dropped, as are records of the jni crate.
+pub struct JavaLogger {
+  backend: LogBackend,
+  level: LevelFilter,
+  cache: Mutex<Cache>,
+}
+
+/// The Java objects used by a JavaLogger, kept as global references
+#[derive(Default)]
+struct Cache {
+  loggers: HashMap<String, GlobalRef>,
+  levels: HashMap<Level, GlobalRef>,
+}
+
+impl JavaLogger {
+  /// Creates a new JavaLogger, logging records at the `Info` level and above
+  ///
+  /// # Arguments
+  ///
+  /// * `backend` - The Java logging API to forward records to
+  pub fn new(backend: LogBackend) -> JavaLogger {
+    JavaLogger {
+      backend,
+      level: LevelFilter::Info,
+      cache: Mutex::new(Cache::default()),
+    }
+  }
+
+  /// Sets the most verbose level to forward
+  ///
+  /// Java can filter records further with the levels of its loggers
+  ///
+  /// # Arguments
+  ///
+  /// * `level` - The level
+  pub fn level(mut self, level: LevelFilter) -> JavaLogger {
+    self.level = level;
+    self
+  }
+
+  /// Installs the logger as the logger of the `log` crate
+  ///
+  /// Returns an error if a logger is already installed
+  pub fn init(self) -> Result<(), SetLoggerError> {
+    let level = self.level;
+
+    log::set_logger(Box::leak(Box::new(self)))?;
+    log::set_max_level(level);
+
+    Ok(())
+  }
+
+  /// Logs a record to Java
+  fn log_to_java(&self, jni_env: &mut JNIEnv, record: &Record) -> jni::errors::Result<()> {
+    let name = logger_name(record.module_path().unwrap_or_else(|| record.target()));
+    let logger = self.logger(jni_env, &name)?;
+    let level = self.level_object(jni_env, record.level())?;
+    let message = jni_env.new_string(record.args().to_string())?;
+
+    let signature = match self.backend {
+      LogBackend::JavaUtilLogging => "(Ljava/util/logging/Level;Ljava/lang/String;)V",
+      LogBackend::SystemLogger => "(Ljava/lang/System$Logger$Level;Ljava/lang/String;)V",
+    };
+
+    jni_env.call_method(
+      logger.as_obj(),
+      "log",
+      signature,
+      &[
+        JValueGen::Object(level.as_obj()),
+        JValueGen::Object(&message),
+      ],
+    )?;
+
+    Ok(())
+  }
+
+  /// Gets the Java logger with a name, creating it the first time
+  fn logger(&self, jni_env: &mut JNIEnv, name: &str) -> jni::errors::Result<GlobalRef> {
+    if let Some(logger) = self.cache().loggers.get(name) {
+      return Ok(logger.clone());
+    }
+
+    let java_name = jni_env.new_string(name)?;
+    let logger = match self.backend {
+      LogBackend::JavaUtilLogging => jul_logger(jni_env, &java_name)?,
+      LogBackend::SystemLogger => system_logger(jni_env, &java_name)?,
+    };
+
+    // java.util.logging only keeps weak references to loggers, so this also keeps their settings
+    let logger = jni_env.new_global_ref(logger)?;
+    self
+      .cache()
+      .loggers
+      .insert(name.to_string(), logger.clone());
+
+    Ok(logger)
+  }
+
+  /// Gets the Java level a Rust level maps to
+  fn level_object(&self, jni_env: &mut JNIEnv, level: Level) -> jni::errors::Result<GlobalRef> {
+    if let Some(object) = self.cache().levels.get(&level) {
+      return Ok(object.clone());
+    }
+
+    let class = self.backend.level_class();
+    let object = jni_env
+      .get_static_field(
+        class,
+        self.backend.level_name(level),
+        format!("L{};", class),
+      )?
+      .l()?;
+
+    let object = jni_env.new_global_ref(object)?;
+    self.cache().levels.insert(level, object.clone());
+
+    Ok(object)
+  }
+
+  /// Locks the cache
+  fn cache(&self) -> std::sync::MutexGuard<'_, Cache> {
+    self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+impl Log for JavaLogger {
+  fn enabled(&self, metadata: &Metadata) -> bool {
+    // jni logs its own calls and thread attachment, which would recurse into the logger
+    let target = metadata.target();
+    let from_jni = target == "jni" || target.starts_with("jni::");
+
+    metadata.level() <= self.level && !from_jni
+  }
+
+  fn log(&self, record: &Record) {
+    if !self.enabled(record.metadata()) {
+      return;
+    }
+
+    let Ok(env) = Env::current() else {
+      return;
+    };
+    let mut jni_env = env.get_jni_env();
+
+    // JNI functions cannot be called while an exception is pending
+    if jni_env.exception_check().unwrap_or(true) {
+      return;
+    }
+
+    // Attached Rust threads never return to Java, so local references are freed with a frame
+    let result = jni_env.with_local_frame(16, |jni_env| self.log_to_java(jni_env, record));
+
+    // Logging must not fail, so exceptions thrown by Java logging are dropped
+    if result.is_err() {
+      let _ = jni_env.exception_clear();
+    }
+  }
+
+  fn flush(&self) {}
+}
+
+/// Gets a `java.util.logging.Logger`, creating it if it does not exist
+///
+/// `Logger.getLogger` needs a Java caller, which attached threads do not have, so the logger is
+/// created with its constructor and added to the LogManager, like `Logger.getLogger` does
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `name` - The name of the logger
+fn jul_logger<'local>(
+  jni_env: &mut JNIEnv<'local>,
+  name: &JString,
+) -> jni::errors::Result<JObject<'local>> {
+  let manager = jni_env
+    .call_static_method(
+      "java/util/logging/LogManager",
+      "getLogManager",
+      "()Ljava/util/logging/LogManager;",
+      &[],
+    )?
+    .l()?;
+  let get_logger = |jni_env: &mut JNIEnv<'local>| {
+    jni_env
+      .call_method(
+        &manager,
+        "getLogger",
+        "(Ljava/lang/String;)Ljava/util/logging/Logger;",
+        &[name.into()],
+      )?
+      .l()
+  };
+
+  let logger = get_logger(jni_env)?;
+  if !logger.is_null() {
+    return Ok(logger);
+  }
+
+  let created = jni_env.new_object(
+    "java/util/logging/Logger",
+    "(Ljava/lang/String;Ljava/lang/String;)V",
+    &[name.into(), (&JObject::null()).into()],
+  )?;
+  jni_env.call_method(
+    &manager,
+    "addLogger",
+    "(Ljava/util/logging/Logger;)Z",
+    &[(&created).into()],
+  )?;
+
+  // Another thread may have added a logger with the name first, in which case it is used
+  get_logger(jni_env)
+}
+
+/// Gets a `System.Logger` from the LoggerFinder, like `System.getLogger` does
+///
+/// `System.getLogger` needs a Java caller, which attached threads do not have, so the logger is
+/// requested for the unnamed module of the system class loader
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `name` - The name of the logger
+fn system_logger<'local>(
+  jni_env: &mut JNIEnv<'local>,
+  name: &JString,
+) -> jni::errors::Result<JObject<'local>> {
+  let finder = jni_env
+    .call_static_method(
+      "java/lang/System$LoggerFinder",
+      "getLoggerFinder",
+      "()Ljava/lang/System$LoggerFinder;",
+      &[],
+    )?
+    .l()?;
+  let loader = jni_env
+    .call_static_method(
+      "java/lang/ClassLoader",
+      "getSystemClassLoader",
+      "()Ljava/lang/ClassLoader;",
+      &[],
+    )?
+    .l()?;
+  let module = jni_env
+    .call_method(&loader, "getUnnamedModule", "()Ljava/lang/Module;", &[])?
+    .l()?;
+
+  jni_env
+    .call_method(
+      &finder,
+      "getLogger",
+      "(Ljava/lang/String;Ljava/lang/Module;)Ljava/lang/System$Logger;",
+      &[name.into(), (&module).into()],
+    )?
+    .l()
+}
+
+/// Converts a Rust module path to the name of a Java logger
+///
+/// # Arguments
+///
+/// * `module_path` - The module path, e.g. `my_crate::net`
+pub(crate) fn logger_name(module_path: &str) -> String {
+  module_path.replace("::", ".")
+}
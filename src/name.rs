@@ -0,0 +1,36 @@
+/// Resolves the binary name of a nested class, e.g. `com/example/Outer.Inner` to
+/// `com/example/Outer$Inner`
+///
+/// Packages are separated with `/` and nested classes with `.`
+///
+/// # Arguments
+///
+/// * `name` - The qualified name of the class
+pub fn binary_name(name: &str) -> String {
+  name.replace('.', "$")
+}
+
+/// Gets the binary name of an anonymous class, e.g. `Outer$1`
+///
+/// javac numbers the anonymous classes of a class from 1, in the order they appear in the source
+///
+/// # Arguments
+///
+/// * `outer` - The qualified name of the enclosing class
+/// * `index` - The index of the anonymous class
+pub fn anonymous_class_name(outer: &str, index: u32) -> String {
+  format!("{}${}", binary_name(outer), index)
+}
+
+/// Gets the binary name of a local class, e.g. `Outer$1Local`
+///
+/// javac numbers the local classes sharing a name from 1, in the order they appear in the source
+///
+/// # Arguments
+///
+/// * `outer` - The qualified name of the enclosing class
+/// * `index` - The index of the local class
+/// * `name` - The simple name of the local class
+pub fn local_class_name(outer: &str, index: u32, name: &str) -> String {
+  format!("{}${}{}", binary_name(outer), index, name)
+}
@@ -1,5 +1,18 @@
-use crate::{env::Env, signature::Signature, value::Value, Class, Type};
-use jni::objects::{JObject, JValueGen};
+use crate::{
+  check,
+  env::Env,
+  local_ref, reflection,
+  signature::Signature,
+  trace::operation,
+  value::{ReturnValue, Value},
+  Class, Type,
+};
+use jni::{
+  objects::{JObject, JStaticFieldID, JStaticMethodID, JString, JValueGen},
+  signature::{JavaType, ReturnType},
+  sys::jvalue,
+};
+use std::str::FromStr;
 
 /// A struct wrapping a JObject
 #[derive(Clone, Copy)]
@@ -21,30 +34,75 @@ impl<'a> Object<'a> {
 
   /// Calls a method on the object
   ///
+  /// Returns `Error::Exception` if the method throws
+  ///
   /// # Arguments
   ///
   /// * `name` - The name of the method
   /// * `signature` - The signature of the method
   /// * `args` - The arguments to pass to the method
+  #[cfg_attr(feature = "leak-check", track_caller)]
   pub fn call_method(
     &self,
     name: &str,
     signature: Signature,
     args: &[Value],
-  ) -> jni::errors::Result<JValueGen<JObject<'_>>> {
-    let signature: String = signature.into();
+  ) -> crate::Result<JValueGen<JObject<'_>>> {
+    let descriptor: String = signature.into();
+    let member = || signature.declaration(name);
 
     let mut jni_env = self.env.get_jni_env();
-    jni_env.call_method(
+    if check::CHECKED {
+      self.check_member(member, check::call(&mut jni_env, signature.arguments, args))?;
+    }
+
+    let operation = operation!(
+      "call_method",
+      class = crate::trace::object_class_name(&mut jni_env, self.object),
+      member = name,
+      descriptor = descriptor
+    );
+    let result = jni_env.call_method(
       self.object,
       name,
-      signature,
+      &descriptor,
       args
         .iter()
         .map(|o| self.env.new_value(*o))
         .collect::<Vec<JValueGen<&JObject>>>()
         .as_slice(),
-    )
+    );
+    operation.finish(&result);
+
+    let value = self.catch_member(member, (name, &descriptor), result)?;
+    local_ref::track_value(&value, "Object::call_method");
+
+    Ok(value)
+  }
+
+  /// Calls a method on the object, returning a value typed by the return type of the signature
+  ///
+  /// Strings are returned as `ReturnValue::String`. Returns `Error::Exception` if the method
+  /// throws.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The name of the method
+  /// * `signature` - The signature of the method
+  /// * `args` - The arguments to pass to the method
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn call_method_typed(
+    &self,
+    name: &str,
+    signature: Signature,
+    args: &[Value],
+  ) -> crate::Result<ReturnValue<'_>> {
+    let value = self.call_method(name, signature, args)?;
+    Ok(ReturnValue::from_jvalue(
+      self.env,
+      value,
+      signature.return_type,
+    ))
   }
 
   /// Gets a field on the object
@@ -53,11 +111,29 @@ impl<'a> Object<'a> {
   ///
   /// * `name` - The name of the field
   /// * `type` - The type of the field
-  pub fn get_field(&self, name: &str, r#type: Type) -> jni::errors::Result<JValueGen<JObject<'_>>> {
-    let r#type: String = r#type.into();
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn get_field(&self, name: &str, r#type: Type) -> crate::Result<JValueGen<JObject<'_>>> {
+    let descriptor: String = r#type.into();
+    let member = || format!("{} {}", r#type, name);
 
     let mut jni_env = self.env.get_jni_env();
-    jni_env.get_field(self.object, name, r#type)
+    if check::CHECKED {
+      self.check_member(member, check::pending_exception(&jni_env))?;
+    }
+
+    let operation = operation!(
+      "get_field",
+      class = crate::trace::object_class_name(&mut jni_env, self.object),
+      member = name,
+      descriptor = descriptor
+    );
+    let result = jni_env.get_field(self.object, name, &descriptor);
+    operation.finish(&result);
+
+    let value = self.catch_member(member, (name, &descriptor), result)?;
+    local_ref::track_value(&value, "Object::get_field");
+
+    Ok(value)
   }
 
   /// Sets a field on the object
@@ -66,11 +142,271 @@ impl<'a> Object<'a> {
   ///
   /// * `name` - The name of the field
   /// * `value` - The value to set the field to
-  pub fn set_field(&self, name: &str, r#type: Type, value: Value) -> jni::errors::Result<()> {
-    let r#type: String = r#type.into();
+  pub fn set_field(&self, name: &str, r#type: Type, value: Value) -> crate::Result<()> {
+    let descriptor: String = r#type.into();
+    let member = || format!("{} {}", r#type, name);
 
     let mut jni_env = self.env.get_jni_env();
-    jni_env.set_field(self.object, name, r#type, self.env.new_value(value))
+    if check::CHECKED {
+      self.check_member(member, check::pending_exception(&jni_env))?;
+      self.check_member(member, check::value_of_type(&mut jni_env, r#type, value))?;
+    }
+
+    let operation = operation!(
+      "set_field",
+      class = crate::trace::object_class_name(&mut jni_env, self.object),
+      member = name,
+      descriptor = descriptor
+    );
+    let result = jni_env.set_field(self.object, name, &descriptor, self.env.new_value(value));
+    operation.finish(&result);
+
+    self.catch_member(member, (name, &descriptor), result)
+  }
+
+  /// Gets a field on the object through reflection, without knowing its type
+  ///
+  /// The field may be private, static or declared by a superclass. Returns `Error::Inaccessible`
+  /// if the field's module does not open it.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The name of the field
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn get_field_dynamic(&self, name: &str) -> crate::Result<JValueGen<JObject<'a>>> {
+    let mut jni_env = self.env.get_jni_env();
+    let operation = operation!(
+      "get_field_dynamic",
+      class = crate::trace::object_class_name(&mut jni_env, self.object),
+      member = name
+    );
+    // The reflection objects are only used here, so they are deleted when dropped
+    let class = self.env.catch(jni_env.get_object_class(self.object))?;
+    let java_name = self.env.catch(jni_env.new_string(name))?;
+    let java_name = jni_env.auto_local(java_name);
+
+    let field = reflection::find_declared(
+      &mut jni_env,
+      class,
+      |jni_env, class| {
+        jni_env
+          .call_method(
+            class,
+            "getDeclaredField",
+            "(Ljava/lang/String;)Ljava/lang/reflect/Field;",
+            &[(&*java_name).into()],
+          )?
+          .l()
+      },
+      "java/lang/NoSuchFieldException",
+    );
+    let field = self
+      .env
+      .catch(field)?
+      .ok_or_else(|| jni::errors::Error::FieldNotFound {
+        name: name.into(),
+        sig: String::new(),
+      })?;
+    let field = jni_env.auto_local(field);
+
+    reflection::set_accessible(&mut jni_env, &field)?;
+
+    let field_type = jni_env.call_method(&field, "getType", "()Ljava/lang/Class;", &[]);
+    let field_type = jni_env.auto_local(self.env.catch(field_type)?.l()?);
+    let descriptor = self
+      .env
+      .catch(reflection::descriptor_for_class(&mut jni_env, &field_type))?;
+    let field_id = reflection::from_reflected_field(&jni_env, &field)?;
+
+    let value = if self
+      .env
+      .catch(reflection::is_static(&mut jni_env, &field))?
+    {
+      let class = self
+        .env
+        .catch(reflection::declaring_class(&mut jni_env, &field))?;
+      let class = jni_env.auto_local(class);
+      let field_id = unsafe { JStaticFieldID::from_raw(field_id.into_raw()) };
+      let field_type = JavaType::from_str(&descriptor)?;
+
+      self
+        .env
+        .catch(jni_env.get_static_field_unchecked(&class, field_id, field_type))?
+    } else {
+      let field_type = ReturnType::from_str(&descriptor)?;
+
+      self
+        .env
+        .catch(jni_env.get_field_unchecked(self.object, field_id, field_type))?
+    };
+    local_ref::track_value(&value, "Object::get_field_dynamic");
+
+    let result = Ok(value);
+    operation.finish(&result);
+
+    result
+  }
+
+  /// Calls a method on the object through reflection
+  ///
+  /// The method may be private, static or declared by a superclass. Returns `Error::Check` if the
+  /// arguments do not match the signature, `Error::Inaccessible` if the method's module does not
+  /// open it, and `Error::Exception` if the method throws.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The name of the method
+  /// * `signature` - The signature of the method
+  /// * `args` - The arguments to pass to the method
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn invoke_private(
+    &self,
+    name: &str,
+    signature: Signature,
+    args: &[Value],
+  ) -> crate::Result<JValueGen<JObject<'a>>> {
+    let descriptor: String = signature.into();
+
+    // The method is called unchecked with the parameters of the signature, so the arguments are
+    // always checked against them, not only in checked builds
+    let mut jni_env = self.env.get_jni_env();
+    let check = check::call(&mut jni_env, signature.arguments, args);
+    self.check_member(|| signature.declaration(name), check)?;
+
+    let operation = operation!(
+      "invoke_private",
+      class = crate::trace::object_class_name(&mut jni_env, self.object),
+      member = name,
+      descriptor = descriptor
+    );
+    // The reflection objects are only used here, so they are deleted when dropped
+    let class = self.env.catch(jni_env.get_object_class(self.object))?;
+    let java_name = self.env.catch(jni_env.new_string(name))?;
+    let java_name = jni_env.auto_local(java_name);
+    let parameters = self
+      .env
+      .catch(reflection::class_array(&mut jni_env, signature.arguments))?;
+    let parameters = jni_env.auto_local(parameters);
+
+    let method = reflection::find_declared(
+      &mut jni_env,
+      class,
+      |jni_env, class| {
+        jni_env
+          .call_method(
+            class,
+            "getDeclaredMethod",
+            "(Ljava/lang/String;[Ljava/lang/Class;)Ljava/lang/reflect/Method;",
+            &[(&*java_name).into(), (&*parameters).into()],
+          )?
+          .l()
+      },
+      "java/lang/NoSuchMethodException",
+    );
+    let method = self
+      .env
+      .catch(method)?
+      .map(|method| jni_env.auto_local(method));
+
+    // getDeclaredMethod ignores the return type, so it is checked separately
+    let method = match method {
+      Some(method) => {
+        let return_type = jni_env.call_method(&method, "getReturnType", "()Ljava/lang/Class;", &[]);
+        let return_type = jni_env.auto_local(self.env.catch(return_type)?.l()?);
+        let return_type = self
+          .env
+          .catch(reflection::descriptor_for_class(&mut jni_env, &return_type))?;
+
+        (return_type == String::from(signature.return_type)).then_some(method)
+      }
+      None => None,
+    }
+    .ok_or_else(|| jni::errors::Error::MethodNotFound {
+      name: name.into(),
+      sig: descriptor,
+    })?;
+
+    reflection::set_accessible(&mut jni_env, &method)?;
+
+    let method_id = reflection::from_reflected_method(&jni_env, &method)?;
+    let return_type = ReturnType::from_str(&String::from(signature.return_type))?;
+    let args = args
+      .iter()
+      .map(|o| self.env.new_value(*o).as_jni())
+      .collect::<Vec<jvalue>>();
+
+    let value = if self
+      .env
+      .catch(reflection::is_static(&mut jni_env, &method))?
+    {
+      let class = self
+        .env
+        .catch(reflection::declaring_class(&mut jni_env, &method))?;
+      let class = jni_env.auto_local(class);
+      let method_id = unsafe { JStaticMethodID::from_raw(method_id.into_raw()) };
+
+      self.env.catch(unsafe {
+        jni_env.call_static_method_unchecked(&class, method_id, return_type, &args)
+      })?
+    } else {
+      self.env.catch(unsafe {
+        jni_env.call_method_unchecked(self.object, method_id, return_type, &args)
+      })?
+    };
+    local_ref::track_value(&value, "Object::invoke_private");
+
+    let result = Ok(value);
+    operation.finish(&result);
+
+    result
+  }
+
+  /// Gets the ordinal of the object, if it is an enum constant
+  pub fn enum_ordinal(&self) -> crate::Result<i32> {
+    let mut jni_env = self.env.get_jni_env();
+    let result = jni_env.call_method(self.object, "ordinal", "()I", &[]);
+
+    Ok(
+      self
+        .catch_member(|| "int ordinal()".into(), ("ordinal", "()I"), result)?
+        .i()?,
+    )
+  }
+
+  /// Gets the name of the object, if it is an enum constant
+  pub fn enum_name(&self) -> crate::Result<String> {
+    let mut jni_env = self.env.get_jni_env();
+    let result = jni_env.call_method(self.object, "name", "()Ljava/lang/String;", &[]);
+    let name = self
+      .catch_member(
+        || "java.lang.String name()".into(),
+        ("name", "()Ljava/lang/String;"),
+        result,
+      )?
+      .l()?;
+    let name = jni_env.auto_local(JString::from(name));
+    let name = jni_env.get_string(&name)?.into();
+
+    Ok(name)
+  }
+
+  /// Checks whether the object is an instance of a class, respecting the class hierarchy
+  ///
+  /// # Arguments
+  ///
+  /// * `class` - The qualified name of the class, e.g. `java/lang/Enum`
+  pub fn is_instance_of(&self, class: &str) -> crate::Result<bool> {
+    let mut jni_env = self.env.get_jni_env();
+    self.env.catch(jni_env.is_instance_of(self.object, class))
+  }
+
+  /// Checks that the object is an instance of a class, returning `Error::WrongClass` if not
+  ///
+  /// # Arguments
+  ///
+  /// * `class` - The qualified name of the class
+  pub(crate) fn check_instance_of(&self, class: &str) -> crate::Result<()> {
+    self.env.check_instance_of(self.object, class)
   }
 
   /// Gets the wrapped object
@@ -79,8 +415,46 @@ impl<'a> Object<'a> {
   }
 
   /// Gets the class of the object
-  pub fn get_class(&self) -> jni::errors::Result<Class> {
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn get_class(&self) -> crate::Result<Class<'_>> {
     let jni_env = self.env.get_jni_env();
-    Ok(Class::new(self.env, jni_env.get_object_class(self.object)?))
+    let class = jni_env.get_object_class(self.object)?;
+    local_ref::track(&class, "Object::get_class");
+
+    Ok(Class::new(self.env, class))
+  }
+
+  /// Adds the class and member that were accessed to the error of a JNI call
+  fn catch_member<T>(
+    &self,
+    member: impl FnOnce() -> String,
+    lookup: (&str, &str),
+    result: jni::errors::Result<T>,
+  ) -> crate::Result<T> {
+    self.env.catch_member(
+      |jni_env| {
+        let class = jni_env.get_object_class(self.object)?;
+        reflection::get_name(jni_env, &class)
+      },
+      |_| member(),
+      lookup,
+      result,
+    )
+  }
+
+  /// Adds the class and member that were accessed to a failed check
+  fn check_member(
+    &self,
+    member: impl FnOnce() -> String,
+    check: jni::errors::Result<Option<String>>,
+  ) -> crate::Result<()> {
+    self.env.check_member(
+      |jni_env| {
+        let class = jni_env.get_object_class(self.object)?;
+        reflection::get_name(jni_env, &class)
+      },
+      |_| member(),
+      check,
+    )
   }
 }
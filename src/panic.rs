@@ -0,0 +1,166 @@
+use crate::env::Env;
+use jni::{
+  objects::{
+    JByteBuffer, JClass, JObject, JObjectArray, JPrimitiveArray, JString, JThrowable, TypeArray,
+  },
+  sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jobject, jshort},
+};
+use std::{
+  any::Any,
+  backtrace::Backtrace,
+  cell::{Cell, RefCell},
+  panic::AssertUnwindSafe,
+  sync::Once,
+};
+
+thread_local! {
+  /// The backtrace of the last panic on this thread, recorded by the panic hook
+  static PANIC_BACKTRACE: RefCell<Option<Backtrace>> = const { RefCell::new(None) };
+
+  /// The number of native functions generated with `jnat!` running on this thread
+  static NATIVE_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A trait for JNI return types, giving the value returned after an exception is thrown
+///
+/// Java ignores the value returned by a native method that throws, so it only has to be valid
+pub trait NullValue {
+  /// Gets the zero or null value of the type
+  fn null_value() -> Self;
+}
+
+macro_rules! null_value {
+  ($value:expr, $($type:ty),+) => {
+    $(
+      impl NullValue for $type {
+        fn null_value() -> Self {
+          $value
+        }
+      }
+    )+
+  };
+}
+
+null_value!((), ());
+null_value!(0, jboolean, jbyte, jchar, jshort, jint, jlong);
+null_value!(0.0, jfloat, jdouble);
+null_value!(std::ptr::null_mut(), jobject);
+
+macro_rules! null_object {
+  ($($type:ident),+) => {
+    $(
+      impl<'a> NullValue for $type<'a> {
+        fn null_value() -> Self {
+          $type::default()
+        }
+      }
+    )+
+  };
+}
+
+null_object!(
+  JObject,
+  JClass,
+  JString,
+  JThrowable,
+  JObjectArray,
+  JByteBuffer
+);
+
+impl<'a, T: TypeArray> NullValue for JPrimitiveArray<'a, T> {
+  fn null_value() -> Self {
+    JPrimitiveArray::default()
+  }
+}
+
+/// Installs a panic hook that records the backtrace of panics in native functions, once
+///
+/// jnat does not install it by itself, as it replaces the process-wide hook. Once installed,
+/// `throw_panic` adds the frames of the backtrace to the stack trace of the exception. Backtraces
+/// are only captured for panics inside functions generated with `jnat!`, and the previous hook is
+/// still called for every panic. It can be called from an `on_load` function.
+pub fn install_panic_hook() {
+  static INSTALL: Once = Once::new();
+
+  INSTALL.call_once(|| {
+    let previous = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+      if NATIVE_DEPTH.with(Cell::get) > 0 {
+        let backtrace = Backtrace::force_capture();
+        PANIC_BACKTRACE.with(|last| *last.borrow_mut() = Some(backtrace));
+      }
+
+      previous(info);
+    }));
+  });
+}
+
+/// Runs a native function, catching a panic so that it can be thrown into Java
+///
+/// The panic hook installed with `install_panic_hook` only captures backtraces while this runs.
+/// This is used by the functions generated with `jnat!`.
+///
+/// # Arguments
+///
+/// * `function` - The native function
+#[doc(hidden)]
+pub fn catch_unwind<R>(function: impl FnOnce() -> R) -> std::thread::Result<R> {
+  NATIVE_DEPTH.with(|depth| depth.set(depth.get() + 1));
+  let result = std::panic::catch_unwind(AssertUnwindSafe(function));
+  NATIVE_DEPTH.with(|depth| depth.set(depth.get() - 1));
+
+  // A panic caught by the function itself is not thrown, so its backtrace is dropped
+  if result.is_ok() {
+    PANIC_BACKTRACE.with(|last| last.borrow_mut().take());
+  }
+
+  result
+}
+
+/// Throws a Rust panic as a Java exception
+///
+/// The message of the exception is the panic message, and its stack trace starts with the Rust
+/// frames that panicked if `install_panic_hook` was called before. An exception that was already pending
+/// is set as its cause. This is used by the functions generated with `jnat!`.
+///
+/// # Arguments
+///
+/// * `env` - The environment
+/// * `payload` - The panic payload, as returned by `std::panic::catch_unwind`
+/// * `class` - The qualified name of the exception's class, e.g. `java/lang/RuntimeException`
+pub fn throw_panic(env: &Env, payload: &(dyn Any + Send), class: &str) {
+  let message = match payload.downcast_ref::<&str>() {
+    Some(message) => message.to_string(),
+    None => match payload.downcast_ref::<String>() {
+      Some(message) => message.clone(),
+      None => "Rust panic".to_string(),
+    },
+  };
+  let message = format!("Rust panic: {}", message);
+
+  let mut jni_env = env.get_jni_env();
+  let pending = match jni_env.exception_check() {
+    Ok(true) => {
+      let pending = jni_env.exception_occurred().ok();
+      let _ = jni_env.exception_clear();
+      pending
+    }
+    _ => None,
+  };
+
+  let backtrace = PANIC_BACKTRACE.with(|last| last.borrow_mut().take());
+
+  let mut throwable = env.throwable(class).message(&message);
+  if let Some(pending) = &pending {
+    throwable = throwable.cause(crate::Object::new(env, pending));
+  }
+  if let Some(backtrace) = &backtrace {
+    throwable = throwable.backtrace(backtrace);
+  }
+
+  // Fall back to RuntimeException if the configured class cannot be thrown
+  if throwable.throw().is_err() {
+    let _ = env.throw_new("java/lang/RuntimeException", &message);
+  }
+}
@@ -0,0 +1,306 @@
+use crate::{error::Error, Type};
+use jni::{
+  errors::Result,
+  objects::{JClass, JFieldID, JMethodID, JObject, JObjectArray, JString},
+  JNIEnv,
+};
+
+/// Calls `getName()` on a reflection object and converts the result
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `object` - The reflection object
+pub(crate) fn get_name(jni_env: &mut JNIEnv, object: &JObject) -> Result<String> {
+  let name = jni_env
+    .call_method(object, "getName", "()Ljava/lang/String;", &[])?
+    .l()?;
+  let name = jni_env.auto_local(JString::from(name));
+  let name = jni_env.get_string(&name)?.into();
+
+  Ok(name)
+}
+
+/// Gets the `Class` object for a type, including primitive and array types
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `type` - The type
+pub(crate) fn class_for_type<'a>(jni_env: &mut JNIEnv<'a>, r#type: Type) -> Result<JClass<'a>> {
+  let wrapper = match r#type {
+    Type::Boolean => "java/lang/Boolean",
+    Type::Byte => "java/lang/Byte",
+    Type::Char => "java/lang/Character",
+    Type::Short => "java/lang/Short",
+    Type::Int => "java/lang/Integer",
+    Type::Long => "java/lang/Long",
+    Type::Float => "java/lang/Float",
+    Type::Double => "java/lang/Double",
+    Type::Void => "java/lang/Void",
+    Type::Object(name) => return jni_env.find_class(name),
+    Type::Array(_) => return jni_env.find_class(String::from(r#type)),
+  };
+
+  // The primitive classes are held by the TYPE field of their wrapper classes
+  let class = jni_env
+    .get_static_field(wrapper, "TYPE", "Ljava/lang/Class;")?
+    .l()?;
+
+  Ok(JClass::from(class))
+}
+
+/// Gets the descriptor of a `Class` object
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `class` - The class
+pub(crate) fn descriptor_for_class(jni_env: &mut JNIEnv, class: &JObject) -> Result<String> {
+  let name = get_name(jni_env, class)?;
+
+  let descriptor = match name.as_str() {
+    "boolean" => "Z".into(),
+    "byte" => "B".into(),
+    "char" => "C".into(),
+    "short" => "S".into(),
+    "int" => "I".into(),
+    "long" => "J".into(),
+    "float" => "F".into(),
+    "double" => "D".into(),
+    "void" => "V".into(),
+    // Array class names are already descriptors, but with dots
+    _ if name.starts_with('[') => name.replace('.', "/"),
+    _ => format!("L{};", name.replace('.', "/")),
+  };
+
+  Ok(descriptor)
+}
+
+/// Finds a declared member in a class or its superclasses
+///
+/// Returns `Ok(None)` if no class in the hierarchy declares the member
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `class` - The class to start from
+/// * `find` - Looks up the member in a single class, e.g. with `getDeclaredField`
+/// * `not_found` - The exception `find` throws if the class does not declare the member
+pub(crate) fn find_declared<'a>(
+  jni_env: &mut JNIEnv<'a>,
+  class: JClass<'a>,
+  find: impl Fn(&mut JNIEnv<'a>, &JClass<'a>) -> Result<JObject<'a>>,
+  not_found: &str,
+) -> Result<Option<JObject<'a>>> {
+  let mut class = Some(class);
+
+  while let Some(current) = class {
+    match find(jni_env, &current) {
+      Err(jni::errors::Error::JavaException) => {
+        let exception = jni_env.exception_occurred()?;
+        jni_env.exception_clear()?;
+        let exception = jni_env.auto_local(exception);
+
+        // Other exceptions are thrown again, for the caller to catch
+        if !jni_env.is_instance_of(&exception, not_found)? {
+          jni_env.delete_local_ref(current)?;
+          jni_env.throw(&*exception)?;
+          return Err(jni::errors::Error::JavaException);
+        }
+      }
+      member => {
+        jni_env.delete_local_ref(current)?;
+        return member.map(Some);
+      }
+    }
+
+    class = jni_env.get_superclass(&current)?;
+    jni_env.delete_local_ref(current)?;
+  }
+
+  Ok(None)
+}
+
+/// Makes a reflected member accessible, suppressing Java access checks
+///
+/// Returns `Error::Inaccessible` if `setAccessible` throws, e.g. because the member's module does
+/// not open its package
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `member` - The reflected member
+pub(crate) fn set_accessible(jni_env: &mut JNIEnv, member: &JObject) -> crate::Result<()> {
+  match jni_env.call_method(member, "setAccessible", "(Z)V", &[true.into()]) {
+    Err(jni::errors::Error::JavaException) => {
+      let exception = jni_env.exception_occurred()?;
+      jni_env.exception_clear()?;
+      let exception = jni_env.auto_local(exception);
+
+      let message = jni_env
+        .call_method(&exception, "getMessage", "()Ljava/lang/String;", &[])?
+        .l()?;
+      let message = jni_env.auto_local(JString::from(message));
+      let message = if message.is_null() {
+        let class = jni_env.get_object_class(&exception)?;
+        let class = jni_env.auto_local(class);
+        get_name(jni_env, &class)?
+      } else {
+        jni_env.get_string(&message)?.into()
+      };
+
+      let class = declaring_class(jni_env, member)?;
+      let class = jni_env.auto_local(class);
+
+      Err(Error::Inaccessible {
+        class: get_name(jni_env, &class)?,
+        member: get_name(jni_env, member)?,
+        message,
+      })
+    }
+    result => Ok(result?.v()?),
+  }
+}
+
+/// Creates a `Class[]` holding the classes of the given types
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `types` - The types
+pub(crate) fn class_array<'a>(
+  jni_env: &mut JNIEnv<'a>,
+  types: &[Type],
+) -> Result<JObjectArray<'a>> {
+  let array = jni_env.new_object_array(types.len() as i32, "java/lang/Class", JObject::null())?;
+
+  for (i, r#type) in types.iter().enumerate() {
+    let class = class_for_type(jni_env, *r#type)?;
+    jni_env.set_object_array_element(&array, i as i32, &class)?;
+    jni_env.delete_local_ref(class)?;
+  }
+
+  Ok(array)
+}
+
+/// Checks whether a reflected member is static
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `member` - The reflected member
+pub(crate) fn is_static(jni_env: &mut JNIEnv, member: &JObject) -> Result<bool> {
+  // java.lang.reflect.Modifier.STATIC
+  const STATIC: i32 = 0x0008;
+
+  let modifiers = jni_env
+    .call_method(member, "getModifiers", "()I", &[])?
+    .i()?;
+
+  Ok(modifiers & STATIC != 0)
+}
+
+/// Checks whether the instances of a nested class are created with an enclosing instance
+///
+/// Static member classes and local records, enums and interfaces have none, nor do local and
+/// anonymous classes declared in a static method. Reflection cannot tell static initializers
+/// apart, so classes declared in initializers are assumed to have one.
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `class` - The nested class
+pub(crate) fn has_enclosing_instance(jni_env: &mut JNIEnv, class: &JObject) -> Result<bool> {
+  if is_static(jni_env, class)? {
+    return Ok(false);
+  }
+
+  if jni_env
+    .call_method(class, "isMemberClass", "()Z", &[])?
+    .z()?
+  {
+    return Ok(true);
+  }
+
+  let method = jni_env
+    .call_method(
+      class,
+      "getEnclosingMethod",
+      "()Ljava/lang/reflect/Method;",
+      &[],
+    )?
+    .l()?;
+  let method = jni_env.auto_local(method);
+
+  Ok(method.is_null() || !is_static(jni_env, &method)?)
+}
+
+/// Gets the class declaring a reflected member
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `member` - The reflected member
+pub(crate) fn declaring_class<'a>(
+  jni_env: &mut JNIEnv<'a>,
+  member: &JObject,
+) -> Result<JClass<'a>> {
+  let class = jni_env
+    .call_method(member, "getDeclaringClass", "()Ljava/lang/Class;", &[])?
+    .l()?;
+
+  Ok(JClass::from(class))
+}
+
+/// Gets the field ID of a `java.lang.reflect.Field`
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `field` - The reflected field
+pub(crate) fn from_reflected_field(jni_env: &JNIEnv, field: &JObject) -> Result<JFieldID> {
+  let raw = jni_env.get_raw();
+
+  let field_id = unsafe {
+    let from_reflected_field =
+      (**raw)
+        .FromReflectedField
+        .ok_or(jni::errors::Error::JNIEnvMethodNotFound(
+          "FromReflectedField",
+        ))?;
+    from_reflected_field(raw, field.as_raw())
+  };
+
+  if field_id.is_null() {
+    return Err(jni::errors::Error::NullPtr("FromReflectedField result"));
+  }
+
+  Ok(unsafe { JFieldID::from_raw(field_id) })
+}
+
+/// Gets the method ID of a `java.lang.reflect.Method`
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `method` - The reflected method
+pub(crate) fn from_reflected_method(jni_env: &JNIEnv, method: &JObject) -> Result<JMethodID> {
+  let raw = jni_env.get_raw();
+
+  let method_id = unsafe {
+    let from_reflected_method =
+      (**raw)
+        .FromReflectedMethod
+        .ok_or(jni::errors::Error::JNIEnvMethodNotFound(
+          "FromReflectedMethod",
+        ))?;
+    from_reflected_method(raw, method.as_raw())
+  };
+
+  if method_id.is_null() {
+    return Err(jni::errors::Error::NullPtr("FromReflectedMethod result"));
+  }
+
+  Ok(unsafe { JMethodID::from_raw(method_id) })
+}
@@ -22,6 +22,36 @@ impl<'a> Signature<'a> {
       return_type,
     }
   }
+
+  /// Formats a method with this signature in Java syntax, e.g. `int indexOf(java.lang.String)`
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The name of the method
+  pub fn declaration(&self, name: &str) -> String {
+    format!("{} {}", self.return_type, self.parameters(name))
+  }
+
+  /// Formats the name and parameter types of a method, e.g. `indexOf(java.lang.String)`
+  fn parameters(&self, name: &str) -> String {
+    let arguments = self
+      .arguments
+      .iter()
+      .map(|a| a.to_string())
+      .collect::<Vec<String>>();
+
+    format!("{}({})", name, arguments.join(", "))
+  }
+
+  /// Formats a constructor with this signature in Java syntax, e.g. `Point(int, int)`
+  ///
+  /// # Arguments
+  ///
+  /// * `class` - The qualified name of the class
+  pub fn constructor_declaration(&self, class: &str) -> String {
+    let class = class.replace('/', ".");
+    self.parameters(class.rsplit('.').next().unwrap_or(&class))
+  }
 }
 
 impl<'a> From<Signature<'a>> for String {
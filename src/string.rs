@@ -0,0 +1,350 @@
+use crate::{array::checked_length, env::Env, error::Error, local_ref};
+use jni::objects::JString;
+use std::{cmp::Ordering, marker::PhantomData, ops::Deref, ptr, slice};
+
+/// The UTF-16 chars of a Java string, borrowed with `GetStringChars` and released on drop
+///
+/// The JVM may copy the chars or pin the string, so this is zero-copy where the JVM allows it
+pub struct StringChars<'a> {
+  raw_env: *mut jni_sys::JNIEnv,
+  string: jni_sys::jstring,
+  chars: *const u16,
+  length: usize,
+  _string: PhantomData<&'a JString<'a>>,
+}
+
+impl<'a> StringChars<'a> {
+  /// Borrows the chars of a string
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `string` - The string
+  pub fn new(env: &Env, string: &'a JString<'a>) -> crate::Result<StringChars<'a>> {
+    if string.is_null() {
+      return Err(jni::errors::Error::NullPtr("string").into());
+    }
+
+    match StringChars::borrow(env, string) {
+      Some(chars) => Ok(chars),
+      None => env.catch_null("GetStringChars"),
+    }
+  }
+
+  /// Borrows the chars of a string that is not null, or returns None with an exception pending
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `string` - The string
+  pub(crate) fn borrow(env: &Env, string: &'a JString<'a>) -> Option<StringChars<'a>> {
+    let raw_env = env.raw_env();
+    let string = string.as_raw() as jni_sys::jstring;
+
+    let functions = unsafe { (**raw_env).v1_1 };
+    let length = unsafe { (functions.GetStringLength)(raw_env, string) } as usize;
+    let chars = unsafe { (functions.GetStringChars)(raw_env, string, ptr::null_mut()) };
+
+    if chars.is_null() {
+      return None;
+    }
+
+    Some(StringChars {
+      raw_env,
+      string,
+      chars,
+      length,
+      _string: PhantomData,
+    })
+  }
+}
+
+impl Drop for StringChars<'_> {
+  fn drop(&mut self) {
+    let functions = unsafe { (**self.raw_env).v1_1 };
+    unsafe { (functions.ReleaseStringChars)(self.raw_env, self.string, self.chars) };
+  }
+}
+
+/// The UTF-16 chars of a Java string, borrowed with `GetStringCritical` and released on drop
+///
+/// This is the most likely to avoid a copy, but the JVM may pause garbage collection until it is
+/// dropped. No JNI function may be called and the thread must not block while it is held, so
+/// only use it for short computations on the chars.
+pub struct StringCritical<'a> {
+  raw_env: *mut jni_sys::JNIEnv,
+  string: jni_sys::jstring,
+  chars: *const u16,
+  length: usize,
+  _string: PhantomData<&'a JString<'a>>,
+}
+
+impl<'a> StringCritical<'a> {
+  /// Borrows the chars of a string in a critical region
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `string` - The string
+  pub fn new(env: &Env, string: &'a JString<'a>) -> crate::Result<StringCritical<'a>> {
+    if string.is_null() {
+      return Err(jni::errors::Error::NullPtr("string").into());
+    }
+
+    let raw_env = env.raw_env();
+    let string = string.as_raw() as jni_sys::jstring;
+
+    // The length is read first, as no JNI function may be called in the critical region
+    let length = unsafe { ((**raw_env).v1_1.GetStringLength)(raw_env, string) } as usize;
+    let chars = unsafe { ((**raw_env).v1_2.GetStringCritical)(raw_env, string, ptr::null_mut()) };
+
+    if chars.is_null() {
+      return env.catch_null("GetStringCritical");
+    }
+
+    Ok(StringCritical {
+      raw_env,
+      string,
+      chars,
+      length,
+      _string: PhantomData,
+    })
+  }
+}
+
+impl Drop for StringCritical<'_> {
+  fn drop(&mut self) {
+    let functions = unsafe { (**self.raw_env).v1_2 };
+    unsafe { (functions.ReleaseStringCritical)(self.raw_env, self.string, self.chars) };
+  }
+}
+
+macro_rules! string_guard {
+  ($($type:ident),+) => {
+    $(
+      impl<'a> $type<'a> {
+        /// Gets the chars as UTF-16 code units
+        pub fn as_slice(&self) -> &[u16] {
+          // GetStringChars may return a dangling pointer for an empty string
+          if self.length == 0 {
+            return &[];
+          }
+
+          unsafe { slice::from_raw_parts(self.chars, self.length) }
+        }
+
+        /// Gets whether every char is ASCII
+        pub fn is_ascii(&self) -> bool {
+          self.as_slice().iter().all(|unit| *unit < 0x80)
+        }
+
+        /// Converts the chars to a Rust string, replacing unpaired surrogates with U+FFFD
+        pub fn to_string_lossy(&self) -> String {
+          String::from_utf16_lossy(self.as_slice())
+        }
+
+        /// Converts the chars to a Rust string
+        ///
+        /// Returns `Error::UnpairedSurrogate` if the string is not valid UTF-16
+        pub fn to_string_strict(&self) -> crate::Result<String> {
+          decode_utf16_strict(self.as_slice())
+        }
+      }
+
+      impl Deref for $type<'_> {
+        type Target = [u16];
+
+        fn deref(&self) -> &[u16] {
+          self.as_slice()
+        }
+      }
+    )+
+  };
+}
+
+string_guard!(StringChars, StringCritical);
+
+/// Decodes UTF-16, returning `Error::UnpairedSurrogate` for the first unpaired surrogate
+///
+/// # Arguments
+///
+/// * `units` - The UTF-16 code units
+pub(crate) fn decode_utf16_strict(units: &[u16]) -> crate::Result<String> {
+  let mut string = String::with_capacity(units.len());
+  let mut index = 0;
+
+  for c in char::decode_utf16(units.iter().copied()) {
+    match c {
+      Ok(c) => {
+        string.push(c);
+        index += c.len_utf16();
+      }
+      Err(error) => {
+        return Err(Error::UnpairedSurrogate {
+          index,
+          unit: error.unpaired_surrogate(),
+        })
+      }
+    }
+  }
+
+  Ok(string)
+}
+
+/// A struct wrapping a JString, with the operations of `java.lang.String`
+pub struct JavaString<'a> {
+  env: &'a Env<'a>,
+  string: JString<'a>,
+}
+
+impl<'a> JavaString<'a> {
+  /// Creates a new JavaString
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `string` - The JString to wrap
+  pub fn new(env: &'a Env<'a>, string: JString<'a>) -> JavaString<'a> {
+    JavaString { env, string }
+  }
+
+  /// Gets the length of the string in UTF-16 code units, like `String.length`
+  pub fn len_utf16(&self) -> crate::Result<usize> {
+    let string = self.raw()?;
+    let raw_env = self.env.raw_env();
+
+    Ok(unsafe { ((**raw_env).v1_1.GetStringLength)(raw_env, string) } as usize)
+  }
+
+  /// Converts the string to a Rust string, replacing unpaired surrogates with U+FFFD
+  ///
+  /// Use `chars` and `StringChars::to_string_strict` to report them instead
+  #[allow(clippy::inherent_to_string)]
+  pub fn to_string(&self) -> crate::Result<String> {
+    self.raw()?;
+    Ok(self.env.get_string_lossy(&self.string)?)
+  }
+
+  /// Gets the UTF-16 code unit at an index, like `String.charAt`
+  ///
+  /// Returns `Error::IndexOutOfBounds` if the index is not in the string
+  ///
+  /// # Arguments
+  ///
+  /// * `index` - The index, in UTF-16 code units
+  pub fn char_at(&self, index: usize) -> crate::Result<u16> {
+    let length = self.len_utf16()?;
+    if index >= length {
+      return Err(Error::IndexOutOfBounds { index, length });
+    }
+
+    let string = self.raw()?;
+    let raw_env = self.env.raw_env();
+
+    let mut unit = 0;
+    let index = checked_length(index)?;
+    unsafe { ((**raw_env).v1_2.GetStringRegion)(raw_env, string, index, 1, &mut unit) };
+
+    Ok(unit)
+  }
+
+  /// Gets the part of the string between two indices, like `String.substring`
+  ///
+  /// Returns `Error::IndexOutOfBounds` if `start` is after `end` or `end` is after the string
+  ///
+  /// # Arguments
+  ///
+  /// * `start` - The index of the first UTF-16 code unit, inclusive
+  /// * `end` - The index of the last UTF-16 code unit, exclusive
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn substring(&self, start: usize, end: usize) -> crate::Result<JavaString<'a>> {
+    let length = self.len_utf16()?;
+    if end > length {
+      return Err(Error::IndexOutOfBounds { index: end, length });
+    }
+    if start > end {
+      return Err(Error::IndexOutOfBounds {
+        index: start,
+        length: end,
+      });
+    }
+
+    let mut jni_env = self.env.get_jni_env();
+    let substring = jni_env.call_method(
+      &self.string,
+      "substring",
+      "(II)Ljava/lang/String;",
+      &[checked_length(start)?.into(), checked_length(end)?.into()],
+    );
+
+    let substring = self.env.catch(substring)?.l()?;
+    local_ref::track(&substring, "JavaString::substring");
+
+    Ok(JavaString::new(self.env, JString::from(substring)))
+  }
+
+  /// Compares the string to another lexicographically, like `String.compareTo`
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The other string
+  pub fn compare(&self, other: &JavaString) -> crate::Result<Ordering> {
+    other.raw()?;
+
+    let mut jni_env = self.env.get_jni_env();
+    let result = jni_env.call_method(
+      &self.string,
+      "compareTo",
+      "(Ljava/lang/String;)I",
+      &[(&other.string).into()],
+    );
+
+    Ok(self.env.catch(result)?.i()?.cmp(&0))
+  }
+
+  /// Gets whether the string is equal to a Rust string, without copying either
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The Rust string
+  pub fn equals_str(&self, other: &str) -> crate::Result<bool> {
+    let chars = self.chars()?;
+    Ok(chars.iter().copied().eq(other.encode_utf16()))
+  }
+
+  /// Gets the canonical instance of the string, like `String.intern`
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn intern(&self) -> crate::Result<JavaString<'a>> {
+    let mut jni_env = self.env.get_jni_env();
+    let result = jni_env.call_method(&self.string, "intern", "()Ljava/lang/String;", &[]);
+
+    let interned = self.env.catch(result)?.l()?;
+    local_ref::track(&interned, "JavaString::intern");
+
+    Ok(JavaString::new(self.env, JString::from(interned)))
+  }
+
+  /// Borrows the UTF-16 chars of the string
+  pub fn chars(&self) -> crate::Result<StringChars<'_>> {
+    StringChars::new(self.env, &self.string)
+  }
+
+  /// Gets the wrapped string
+  pub fn get_string(&self) -> &JString<'a> {
+    &self.string
+  }
+
+  /// Unwraps the string
+  pub(crate) fn into_string(self) -> JString<'a> {
+    self.string
+  }
+
+  /// Gets the wrapped string, returning a `NullPtr` error if it is null
+  fn raw(&self) -> crate::Result<jni_sys::jstring> {
+    if self.string.is_null() {
+      return Err(jni::errors::Error::NullPtr("string").into());
+    }
+
+    Ok(self.string.as_raw() as jni_sys::jstring)
+  }
+}
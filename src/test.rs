@@ -20,4 +20,234 @@ mod signature {
     .into();
     assert_eq!(signature, "([Ljava/lang/String;[C)C");
   }
+
+  #[test]
+  fn signature_declaration() {
+    let signature = Signature::new(&[], Type::Void);
+    assert_eq!(signature.declaration("run"), "void run()");
+
+    let signature = Signature::new(
+      &[
+        Type::Object("java/lang/String"),
+        Type::Array(&Type::Array(&Type::Int)),
+      ],
+      Type::Boolean,
+    );
+    assert_eq!(
+      signature.declaration("check"),
+      "boolean check(java.lang.String, int[][])"
+    );
+    assert_eq!(
+      signature.constructor_declaration("com/example/Point"),
+      "Point(java.lang.String, int[][])"
+    );
+    let signature = Signature::new(&[Type::Int], Type::Object(""));
+    assert_eq!(signature.declaration("twice"), "L; twice(int)");
+  }
+}
+
+#[cfg(test)]
+mod name {
+  use crate::name::{anonymous_class_name, binary_name, local_class_name};
+
+  #[test]
+  fn binary_name_from() {
+    assert_eq!(binary_name("Outer"), "Outer");
+    assert_eq!(binary_name("Outer.Inner"), "Outer$Inner");
+    assert_eq!(
+      binary_name("com/example/Outer.Inner.Deep"),
+      "com/example/Outer$Inner$Deep"
+    );
+  }
+
+  #[test]
+  fn generated_class_names() {
+    assert_eq!(
+      anonymous_class_name("com/example/Outer", 1),
+      "com/example/Outer$1"
+    );
+    assert_eq!(
+      anonymous_class_name("com/example/Outer.Inner", 2),
+      "com/example/Outer$Inner$2"
+    );
+    assert_eq!(
+      local_class_name("com/example/Outer", 1, "Local"),
+      "com/example/Outer$1Local"
+    );
+  }
+}
+
+#[cfg(test)]
+mod backtrace {
+  use crate::backtrace::parse_backtrace;
+
+  #[test]
+  fn parse() {
+    let frames = parse_backtrace(
+      "   0: std::backtrace::Backtrace::force_capture
+             at /rustc/library/std/src/backtrace.rs:312:9
+   1: core::panicking::panic_fmt
+   2: example::parse::{{closure}}
+             at ./src/parse.rs:12:5
+      example::parse
+             at ./src/parse.rs:20:3
+   3: <alloc::boxed::Box<F> as core::ops::function::Fn<A>>::call
+   4: Java_Example_parse
+   5: <unknown>",
+    );
+
+    let frames = frames
+      .iter()
+      .map(|frame| frame.to_string())
+      .collect::<Vec<String>>();
+
+    assert_eq!(
+      frames,
+      [
+        "example::parse.{{closure}}(parse.rs:12)",
+        "example.parse(parse.rs:20)",
+        ".Java_Example_parse(Unknown Source)",
+      ]
+    );
+  }
+}
+
+#[cfg(test)]
+mod version {
+  use crate::version::Version;
+
+  #[test]
+  fn version_raw() {
+    assert_eq!(Version::from_raw(0x00010008), Version::V1_8);
+    assert_eq!(Version::V21.to_raw(), 0x00150000);
+    assert_eq!(Version::from_raw(0x00190000), Version::Unknown(0x00190000));
+  }
+
+  #[test]
+  fn version_order() {
+    assert!(Version::V1_8 < Version::V9);
+    assert!(Version::V21 > Version::V1_6);
+    assert!(Version::Unknown(0x00190000) > Version::V24);
+
+    assert_eq!(Version::Unknown(0x00010008), Version::V1_8);
+    assert_eq!(
+      Version::Unknown(0x00010008).cmp(&Version::V1_8),
+      std::cmp::Ordering::Equal
+    );
+
+    assert_eq!(Version::V1_8.to_string(), "1.8");
+    assert_eq!(Version::V21.to_string(), "21");
+  }
+}
+
+#[cfg(test)]
+mod array {
+  use crate::{array::checked_length, error::Error};
+
+  #[test]
+  fn length() {
+    assert_eq!(checked_length(3).unwrap(), 3);
+    assert_eq!(checked_length(i32::MAX as usize).unwrap(), i32::MAX);
+
+    assert!(matches!(
+      checked_length(i32::MAX as usize + 1),
+      Err(Error::LengthTooLarge { length }) if length == i32::MAX as usize + 1
+    ));
+  }
+}
+
+#[cfg(test)]
+mod error {
+  use crate::error::Error;
+  use std::error::Error as _;
+
+  #[test]
+  fn jni_source() {
+    let error = Error::Jni(jni::errors::Error::NullPtr("object"));
+    assert_eq!(error.source().unwrap().to_string(), error.to_string());
+  }
+}
+
+#[cfg(test)]
+mod string {
+  use crate::{error::Error, string::decode_utf16_strict};
+
+  #[test]
+  fn decode_strict() {
+    let units = "h\u{e9}llo \u{1f600}".encode_utf16().collect::<Vec<u16>>();
+    assert_eq!(decode_utf16_strict(&units).unwrap(), "h\u{e9}llo \u{1f600}");
+
+    match decode_utf16_strict(&[0x61, 0xd83d, 0x62]) {
+      Err(Error::UnpairedSurrogate { index, unit }) => {
+        assert_eq!(index, 1);
+        assert_eq!(unit, 0xd83d);
+      }
+      _ => panic!("expected an unpaired surrogate"),
+    }
+
+    assert!(matches!(
+      decode_utf16_strict(&[0x61, 0xde00]),
+      Err(Error::UnpairedSurrogate { index: 1, .. })
+    ));
+  }
+}
+
+#[cfg(all(test, feature = "log"))]
+mod logger {
+  use crate::logger::logger_name;
+
+  #[test]
+  fn name() {
+    assert_eq!(logger_name("my_crate"), "my_crate");
+    assert_eq!(logger_name("my_crate::net::http"), "my_crate.net.http");
+  }
+}
+
+#[cfg(all(test, feature = "leak-check"))]
+mod local_ref {
+  use crate::{
+    local_ref::{track, untrack},
+    LocalRefScope,
+  };
+  use jni::objects::JObject;
+
+  // The references are only compared, never passed to the JVM
+  fn fake(raw: usize) -> JObject<'static> {
+    unsafe { JObject::from_raw(raw as jni::sys::jobject) }
+  }
+
+  #[test]
+  fn scope() {
+    let (first, second) = (fake(0x10), fake(0x20));
+
+    let outer = LocalRefScope::new();
+    track(&first, "first");
+    let line = line!() - 1;
+
+    let inner = LocalRefScope::new();
+    track(&second, "second");
+    track(&JObject::null(), "null");
+    assert_eq!(outer.count(), 2);
+    assert_eq!(inner.count(), 1);
+
+    untrack(&second);
+    inner.assert_balanced();
+    drop(inner);
+
+    let leaked = outer.leaked();
+    assert_eq!(leaked.len(), 1);
+    assert_eq!(leaked[0].function(), "first");
+    assert_eq!(leaked[0].location().line(), line);
+    assert!(leaked[0].to_string().starts_with("first at src/test.rs:"));
+
+    untrack(&first);
+    outer.assert_balanced();
+  }
+
+  #[test]
+  #[should_panic(expected = "1 local references were not deleted, created by:\n  leaked at")]
+  fn unbalanced() {
+    let _scope = LocalRefScope::new();
+    track(&fake(0x30), "leaked");
+  }
 }
@@ -0,0 +1,187 @@
+use crate::{
+  backtrace::{rust_stack_trace, splice_stack_trace},
+  env::Env,
+  error::Error,
+  exception::Exception,
+  local_ref,
+  object::Object,
+};
+use jni::objects::{JObject, JValueGen};
+use std::{backtrace::Backtrace, fmt::Display};
+
+/// A trait for Rust errors that map to a Java exception class
+pub trait JavaThrowable: Display {
+  /// Gets the qualified name of the Java exception class, e.g. `java/io/IOException`
+  ///
+  /// The class must have a constructor taking a single `String`
+  fn java_class(&self) -> &str;
+
+  /// Gets the backtrace captured when the error was created, if there is one
+  ///
+  /// Its frames are added to the stack trace of the Java exception
+  fn backtrace(&self) -> Option<&Backtrace> {
+    None
+  }
+
+  /// Throws the error as a Java exception, using its `Display` output as the message
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  fn throw(&self, env: &Env) -> crate::Result<()> {
+    let message = self.to_string();
+    let mut throwable = env.throwable(self.java_class()).message(&message);
+
+    if let Some(backtrace) = self.backtrace() {
+      throwable = throwable.backtrace(backtrace);
+    }
+
+    throwable.throw()
+  }
+}
+
+impl JavaThrowable for Error {
+  fn java_class(&self) -> &str {
+    match self {
+      Error::Jni(_) | Error::Call { .. } => "java/lang/RuntimeException",
+      Error::Check { .. }
+      | Error::LengthTooLarge { .. }
+      | Error::SurrogateChar { .. }
+      | Error::UnpairedSurrogate { .. } => "java/lang/IllegalArgumentException",
+      Error::WrongClass { .. } => "java/lang/ClassCastException",
+      Error::IndexOutOfBounds { .. } => "java/lang/ArrayIndexOutOfBoundsException",
+      Error::Invocation(_) | Error::NoJavaVm => "java/lang/IllegalStateException",
+      Error::Unsupported { .. } => "java/lang/UnsupportedOperationException",
+      // Only used by the default `throw`, which is overridden to rethrow the original throwable
+      Error::Exception(_) => "java/lang/Throwable",
+      Error::Inaccessible { .. } => "java/lang/IllegalAccessException",
+      // ReadOnlyBufferException has no constructor taking a message, so its superclass is used
+      Error::ReadOnlyBuffer => "java/lang/UnsupportedOperationException",
+    }
+  }
+
+  fn throw(&self, env: &Env) -> crate::Result<()> {
+    match self {
+      Error::Exception(exception) => Ok(exception.rethrow(env)?),
+      _ => env.throw_new(self.java_class(), &self.to_string()),
+    }
+  }
+}
+
+impl JavaThrowable for Exception {
+  fn java_class(&self) -> &str {
+    // Only used by the default `throw`, which is overridden to rethrow the original throwable
+    "java/lang/Throwable"
+  }
+
+  fn throw(&self, env: &Env) -> crate::Result<()> {
+    Ok(self.rethrow(env)?)
+  }
+}
+
+/// A builder for Java throwables
+pub struct ThrowableBuilder<'a> {
+  env: &'a Env<'a>,
+  class: &'a str,
+  message: Option<&'a str>,
+  cause: Option<Object<'a>>,
+  backtrace: Option<&'a Backtrace>,
+}
+
+impl<'a> ThrowableBuilder<'a> {
+  /// Creates a new ThrowableBuilder
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `class` - The qualified name of the throwable's class, e.g. `java/io/IOException`
+  pub fn new(env: &'a Env<'a>, class: &'a str) -> ThrowableBuilder<'a> {
+    ThrowableBuilder {
+      env,
+      class,
+      message: None,
+      cause: None,
+      backtrace: None,
+    }
+  }
+
+  /// Sets the message of the throwable
+  ///
+  /// # Arguments
+  ///
+  /// * `message` - The message
+  pub fn message(mut self, message: &'a str) -> ThrowableBuilder<'a> {
+    self.message = Some(message);
+    self
+  }
+
+  /// Sets the cause of the throwable
+  ///
+  /// # Arguments
+  ///
+  /// * `cause` - The cause, which must be a throwable
+  pub fn cause(mut self, cause: Object<'a>) -> ThrowableBuilder<'a> {
+    self.cause = Some(cause);
+    self
+  }
+
+  /// Adds the frames of a Rust backtrace in front of the stack trace of the throwable
+  ///
+  /// # Arguments
+  ///
+  /// * `backtrace` - The backtrace
+  pub fn backtrace(mut self, backtrace: &'a Backtrace) -> ThrowableBuilder<'a> {
+    self.backtrace = Some(backtrace);
+    self
+  }
+
+  /// Creates the throwable without throwing it
+  #[cfg_attr(feature = "leak-check", track_caller)]
+  pub fn build(&self) -> crate::Result<JObject<'a>> {
+    // Checked first, so that no throwable is left behind
+    if let Some(cause) = self.cause {
+      self
+        .env
+        .check_instance_of(cause.get_object(), "java/lang/Throwable")?;
+    }
+
+    let mut jni_env = self.env.get_jni_env();
+
+    let throwable = match self.message {
+      Some(message) => {
+        let message = self.env.catch(jni_env.new_string(message))?;
+        let message = jni_env.auto_local(message);
+        jni_env.new_object(self.class, "(Ljava/lang/String;)V", &[(&*message).into()])
+      }
+      None => jni_env.new_object(self.class, "()V", &[]),
+    };
+    let throwable = self.env.catch(throwable)?;
+    local_ref::track(&throwable, "ThrowableBuilder::build");
+
+    if let Some(cause) = self.cause {
+      // initCause returns the throwable itself, as a new local reference
+      let result = self.env.catch(jni_env.call_method(
+        &throwable,
+        "initCause",
+        "(Ljava/lang/Throwable;)Ljava/lang/Throwable;",
+        &[JValueGen::Object(cause.get_object())],
+      ))?;
+      jni_env.delete_local_ref(result.l()?)?;
+    }
+
+    if let Some(backtrace) = self.backtrace {
+      splice_stack_trace(self.env, &throwable, &rust_stack_trace(backtrace))?;
+    }
+
+    Ok(throwable)
+  }
+
+  /// Creates the throwable and throws it
+  pub fn throw(&self) -> crate::Result<()> {
+    let throwable = self.build()?;
+    self.env.throw(&Object::new(self.env, &throwable))?;
+
+    // The pending exception keeps the throwable alive
+    self.env.delete_local_ref(throwable)
+  }
+}
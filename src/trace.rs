@@ -0,0 +1,135 @@
+#[cfg(feature = "tracing")]
+use jni::{objects::JObject, JNIEnv};
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+/// Starts an operation, entering a span named after it with the `tracing` feature
+///
+/// The class is only evaluated if a subscriber is interested in the span, as getting the name of
+/// a class takes more JNI calls. The other fields are left empty when omitted.
+///
+/// # Arguments
+///
+/// * `name` - The name of the span, e.g. `"call_method"`
+/// * `class` - An `Option<String>` with the name of the class
+/// * `member` - The name of the method or field
+/// * `descriptor` - The descriptor of the method or field
+macro_rules! operation {
+  ($name:literal, class = $class:expr $(, member = $member:expr)? $(, descriptor = $descriptor:expr)?) => {{
+    #[cfg(feature = "tracing")]
+    let operation = {
+      let span = tracing::trace_span!(
+        target: "jnat",
+        $name,
+        class = tracing::field::Empty,
+        member = tracing::field::Empty,
+        descriptor = tracing::field::Empty,
+        duration_us = tracing::field::Empty,
+        error = tracing::field::Empty,
+      );
+
+      if !span.is_disabled() {
+        let class: Option<String> = $class;
+        if let Some(class) = class {
+          span.record("class", class.as_str());
+        }
+        $(span.record("member", AsRef::<str>::as_ref(&$member));)?
+        $(span.record("descriptor", AsRef::<str>::as_ref(&$descriptor));)?
+      }
+
+      crate::trace::Operation::new(span)
+    };
+
+    #[cfg(not(feature = "tracing"))]
+    let operation = crate::trace::Operation;
+
+    operation
+  }};
+}
+
+pub(crate) use operation;
+
+/// A JNI operation, whose span records its duration and whether it failed when finished
+///
+/// The duration is recorded when the operation is dropped, so an operation left early, e.g. by
+/// `?`, is still recorded, as failed
+#[cfg(feature = "tracing")]
+pub(crate) struct Operation {
+  span: tracing::span::EnteredSpan,
+  start: Instant,
+  failed: bool,
+}
+
+#[cfg(feature = "tracing")]
+impl Operation {
+  /// Enters the span of an operation
+  pub(crate) fn new(span: tracing::Span) -> Operation {
+    Operation {
+      span: span.entered(),
+      start: Instant::now(),
+      failed: true,
+    }
+  }
+
+  /// Records whether the operation failed, and exits its span
+  ///
+  /// # Arguments
+  ///
+  /// * `result` - The result of the operation
+  pub(crate) fn finish<T, E>(mut self, result: &Result<T, E>) {
+    self.failed = result.is_err();
+  }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for Operation {
+  fn drop(&mut self) {
+    let duration = self.start.elapsed().as_micros() as u64;
+
+    self.span.record("duration_us", duration);
+    self.span.record("error", self.failed);
+  }
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Operation;
+
+#[cfg(not(feature = "tracing"))]
+impl Operation {
+  #[inline(always)]
+  pub(crate) fn finish<T, E>(self, _: &Result<T, E>) {}
+}
+
+/// Gets the name of a class for a span, e.g. `java.lang.String`
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `class` - The class
+#[cfg(feature = "tracing")]
+pub(crate) fn class_name(jni_env: &mut JNIEnv, class: &JObject) -> Option<String> {
+  // No JNI function may be called while an exception is pending
+  if jni_env.exception_check().unwrap_or(true) {
+    return None;
+  }
+
+  crate::reflection::get_name(jni_env, class).ok()
+}
+
+/// Gets the name of the class of an object for a span
+///
+/// # Arguments
+///
+/// * `jni_env` - The JNI environment
+/// * `object` - The object
+#[cfg(feature = "tracing")]
+pub(crate) fn object_class_name(jni_env: &mut JNIEnv, object: &JObject) -> Option<String> {
+  if object.is_null() || jni_env.exception_check().unwrap_or(true) {
+    return None;
+  }
+
+  let class = jni_env.get_object_class(object).ok()?;
+  let class = jni_env.auto_local(class);
+
+  class_name(jni_env, &class)
+}
@@ -1,3 +1,5 @@
+use std::fmt::{self, Display, Formatter};
+
 /// An enum representing Java types
 #[derive(Clone, Copy)]
 pub enum Type<'a> {
@@ -44,3 +46,24 @@ impl<'a> From<Type<'a>> for String {
     result
   }
 }
+
+impl<'a> Display for Type<'a> {
+  /// Formats the type in Java syntax, e.g. `int` or `java.lang.String[]`
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Type::Boolean => write!(f, "boolean"),
+      Type::Byte => write!(f, "byte"),
+      Type::Char => write!(f, "char"),
+      Type::Short => write!(f, "short"),
+      Type::Int => write!(f, "int"),
+      Type::Long => write!(f, "long"),
+      Type::Float => write!(f, "float"),
+      Type::Double => write!(f, "double"),
+      Type::Void => write!(f, "void"),
+      // An empty name has no Java syntax, so its descriptor is shown instead
+      Type::Object("") => write!(f, "L;"),
+      Type::Object(s) => write!(f, "{}", s.replace('/', ".")),
+      Type::Array(t) => write!(f, "{}[]", t),
+    }
+  }
+}
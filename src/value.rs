@@ -1,4 +1,5 @@
-use crate::object::Object;
+use crate::{env::Env, object::Object, r#type::Type, string::JavaString};
+use jni::objects::{JObject, JString, JValueGen};
 
 /// An enum representing Java values
 #[derive(Clone, Copy)]
@@ -24,4 +25,93 @@ pub enum Value<'a> {
   Void,
   /// An object value
   Object(Object<'a>),
+  /// A string value
+  String(&'a JavaString<'a>),
+}
+
+impl<'a> Value<'a> {
+  /// Gets the object of an object or string value
+  pub(crate) fn as_object(&self) -> Option<&'a JObject<'a>> {
+    match self {
+      Value::Object(object) => Some(object.get_object()),
+      Value::String(string) => Some(string.get_string()),
+      _ => None,
+    }
+  }
+}
+
+/// A Java value returned by a method or field, typed by its declared `Type`
+pub enum ReturnValue<'a> {
+  /// A boolean value
+  Boolean(bool),
+  /// A byte value
+  Byte(i8),
+  /// A char value, as a UTF-16 code unit
+  Char(u16),
+  /// A short value
+  Short(i16),
+  /// An int value
+  Int(i32),
+  /// A long value
+  Long(i64),
+  /// A float value
+  Float(f32),
+  /// A double value
+  Double(f64),
+  /// No value, returned by void methods
+  Void,
+  /// A non-null string, returned when the type is `java/lang/String`
+  String(JavaString<'a>),
+  /// Any other object or array, which may be null
+  Object(JObject<'a>),
+}
+
+impl<'a> ReturnValue<'a> {
+  /// Converts a value returned by the jni crate, given its declared type
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  /// * `value` - The value
+  /// * `type` - The declared type of the value
+  pub fn from_jvalue(
+    env: &'a Env<'a>,
+    value: JValueGen<JObject<'a>>,
+    r#type: Type,
+  ) -> ReturnValue<'a> {
+    match value {
+      JValueGen::Bool(b) => ReturnValue::Boolean(b != 0),
+      JValueGen::Byte(b) => ReturnValue::Byte(b),
+      JValueGen::Char(c) => ReturnValue::Char(c),
+      JValueGen::Short(s) => ReturnValue::Short(s),
+      JValueGen::Int(i) => ReturnValue::Int(i),
+      JValueGen::Long(l) => ReturnValue::Long(l),
+      JValueGen::Float(f) => ReturnValue::Float(f),
+      JValueGen::Double(d) => ReturnValue::Double(d),
+      JValueGen::Void => ReturnValue::Void,
+      JValueGen::Object(o) => match r#type {
+        Type::Object("java/lang/String") if !o.is_null() => {
+          ReturnValue::String(JavaString::new(env, JString::from(o)))
+        }
+        _ => ReturnValue::Object(o),
+      },
+    }
+  }
+
+  /// Gets the name of the type of the value, as used by `Error::WrongJValueType`
+  pub(crate) fn type_name(&self) -> &'static str {
+    match self {
+      ReturnValue::Boolean(_) => "bool",
+      ReturnValue::Byte(_) => "byte",
+      ReturnValue::Char(_) => "char",
+      ReturnValue::Short(_) => "short",
+      ReturnValue::Int(_) => "int",
+      ReturnValue::Long(_) => "long",
+      ReturnValue::Float(_) => "float",
+      ReturnValue::Double(_) => "double",
+      ReturnValue::Void => "void",
+      ReturnValue::String(_) => "string",
+      ReturnValue::Object(_) => "object",
+    }
+  }
 }
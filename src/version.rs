@@ -0,0 +1,138 @@
+use jni::sys::{
+  jint, JNI_VERSION_10, JNI_VERSION_19, JNI_VERSION_1_1, JNI_VERSION_1_2, JNI_VERSION_1_4,
+  JNI_VERSION_1_6, JNI_VERSION_1_8, JNI_VERSION_20, JNI_VERSION_21, JNI_VERSION_24, JNI_VERSION_9,
+};
+use std::{
+  cmp::Ordering,
+  fmt::{self, Display, Formatter},
+  hash::{Hash, Hasher},
+};
+
+/// A JNI version, which tells which JNI functions are available
+///
+/// Versions are compared by their raw value, so `Unknown` with the raw value of a known version
+/// is equal to it
+#[derive(Debug, Clone, Copy)]
+pub enum Version {
+  /// JNI 1.1
+  V1_1,
+  /// JNI 1.2
+  V1_2,
+  /// JNI 1.4
+  V1_4,
+  /// JNI 1.6
+  V1_6,
+  /// JNI 1.8
+  V1_8,
+  /// JNI 9, which adds `GetModule`
+  V9,
+  /// JNI 10
+  V10,
+  /// JNI 19
+  V19,
+  /// JNI 20
+  V20,
+  /// JNI 21, which adds `IsVirtualThread`
+  V21,
+  /// JNI 24
+  V24,
+  /// A version jnat does not know, given as the raw value
+  Unknown(jint),
+}
+
+impl Version {
+  /// The versions jnat knows, newest first
+  pub(crate) const KNOWN: [Version; 11] = [
+    Version::V24,
+    Version::V21,
+    Version::V20,
+    Version::V19,
+    Version::V10,
+    Version::V9,
+    Version::V1_8,
+    Version::V1_6,
+    Version::V1_4,
+    Version::V1_2,
+    Version::V1_1,
+  ];
+
+  /// Converts a raw version, as used by JNI
+  ///
+  /// # Arguments
+  ///
+  /// * `version` - The raw version, e.g. `0x00010008` for 1.8
+  pub fn from_raw(version: jint) -> Version {
+    match version {
+      JNI_VERSION_1_1 => Version::V1_1,
+      JNI_VERSION_1_2 => Version::V1_2,
+      JNI_VERSION_1_4 => Version::V1_4,
+      JNI_VERSION_1_6 => Version::V1_6,
+      JNI_VERSION_1_8 => Version::V1_8,
+      JNI_VERSION_9 => Version::V9,
+      JNI_VERSION_10 => Version::V10,
+      JNI_VERSION_19 => Version::V19,
+      JNI_VERSION_20 => Version::V20,
+      JNI_VERSION_21 => Version::V21,
+      JNI_VERSION_24 => Version::V24,
+      version => Version::Unknown(version),
+    }
+  }
+
+  /// Gets the raw version, as used by JNI
+  pub fn to_raw(self) -> jint {
+    match self {
+      Version::V1_1 => JNI_VERSION_1_1,
+      Version::V1_2 => JNI_VERSION_1_2,
+      Version::V1_4 => JNI_VERSION_1_4,
+      Version::V1_6 => JNI_VERSION_1_6,
+      Version::V1_8 => JNI_VERSION_1_8,
+      Version::V9 => JNI_VERSION_9,
+      Version::V10 => JNI_VERSION_10,
+      Version::V19 => JNI_VERSION_19,
+      Version::V20 => JNI_VERSION_20,
+      Version::V21 => JNI_VERSION_21,
+      Version::V24 => JNI_VERSION_24,
+      Version::Unknown(version) => version,
+    }
+  }
+}
+
+impl PartialEq for Version {
+  fn eq(&self, other: &Self) -> bool {
+    self.to_raw() == other.to_raw()
+  }
+}
+
+impl Eq for Version {}
+
+impl Hash for Version {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.to_raw().hash(state);
+  }
+}
+
+impl PartialOrd for Version {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for Version {
+  fn cmp(&self, other: &Self) -> Ordering {
+    // The major version is in the high 16 bits and the minor version in the low 16 bits
+    self.to_raw().cmp(&other.to_raw())
+  }
+}
+
+impl Display for Version {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let version = self.to_raw();
+    let (major, minor) = (version >> 16, version & 0xffff);
+
+    if major == 1 {
+      write!(f, "{}.{}", major, minor)
+    } else {
+      write!(f, "{}", major)
+    }
+  }
+}
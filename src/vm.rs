@@ -0,0 +1,259 @@
+use crate::{env::Env, error::Error, version::Version};
+use jni::{
+  sys::{self, jint, JNI_ERR, JNI_EVERSION, JNI_OK},
+  JNIEnv, JavaVM,
+};
+
+pub use jni::AttachGuard;
+use std::{cell::RefCell, ptr, sync::OnceLock};
+
+/// The JVM stored with `JavaVm::store`
+static GLOBAL: OnceLock<JavaVm> = OnceLock::new();
+
+thread_local! {
+  /// The JNI environment of this thread, once `Env::current` or `attach_as_daemon` was called
+  ///
+  /// It is boxed so that its address stays the same, and the thread stays attached until it exits
+  static CURRENT: RefCell<Option<Current>> = const { RefCell::new(None) };
+}
+
+/// The environment of a thread, with the JVM it is attached to
+struct Current {
+  jni_env: Box<JNIEnv<'static>>,
+  vm: JavaVM,
+}
+
+/// A handle to a Java virtual machine, used to attach native threads to it
+///
+/// A handle to the JVM that loaded the library can be created with `JavaVm::from_env`, and one
+/// to a JVM launched from Rust with `JavaVm::builder` (with the `invocation` feature).
+pub struct JavaVm {
+  vm: JavaVM,
+  // libjvm must stay loaded while the JVM runs, and is only set if this created the JVM
+  #[cfg(feature = "invocation")]
+  library: Option<libloading::Library>,
+}
+
+impl JavaVm {
+  /// Creates a new JavaVm, which does not destroy the JVM when dropped
+  ///
+  /// # Arguments
+  ///
+  /// * `vm` - The JavaVM to wrap
+  pub fn new(vm: JavaVM) -> JavaVm {
+    JavaVm {
+      vm,
+      #[cfg(feature = "invocation")]
+      library: None,
+    }
+  }
+
+  /// Creates a new JavaVm, which destroys the JVM when dropped
+  #[cfg(feature = "invocation")]
+  pub(crate) fn created(vm: JavaVM, library: libloading::Library) -> JavaVm {
+    JavaVm {
+      vm,
+      library: Some(library),
+    }
+  }
+
+  /// Gets the JVM of an environment
+  ///
+  /// # Arguments
+  ///
+  /// * `env` - The environment
+  pub fn from_env(env: &Env) -> crate::Result<JavaVm> {
+    Ok(JavaVm::new(env.get_jni_env().get_java_vm()?))
+  }
+
+  /// Creates a builder for a JVM
+  #[cfg(feature = "invocation")]
+  pub fn builder() -> crate::JavaVmBuilder {
+    crate::JavaVmBuilder::new()
+  }
+
+  /// Stores the JVM globally, for `Env::current` and `JavaVm::global`, and gets the stored JVM
+  ///
+  /// A stored JVM is never destroyed, as other threads may use it until the process exits. Only
+  /// the first JVM stored is kept, as a process only has one JVM. If one is already stored, this
+  /// handle is dropped without destroying its JVM, and the stored one is returned.
+  pub fn store(self) -> &'static JavaVm {
+    let mut vm = Some(self);
+    let stored = GLOBAL.get_or_init(|| vm.take().expect("the JVM is only taken once"));
+
+    // Destroying the JVM or unloading libjvm would break the stored handle, so both are leaked
+    #[cfg(feature = "invocation")]
+    if let Some(vm) = vm {
+      std::mem::forget(vm);
+    }
+
+    stored
+  }
+
+  /// Gets the JVM stored with `JavaVm::store`, if there is one
+  pub fn global() -> Option<&'static JavaVm> {
+    GLOBAL.get()
+  }
+
+  /// Attaches the current thread to the JVM until the returned guard is dropped
+  ///
+  /// If the thread is already attached, it stays attached after the guard is dropped. Wrap the
+  /// guard with `Env::new` to use jnat.
+  pub fn attach_current_thread(&self) -> crate::Result<AttachGuard<'_>> {
+    Ok(self.vm.attach_current_thread()?)
+  }
+
+  /// Attaches the current thread to the JVM as a daemon thread, until the thread exits
+  ///
+  /// The JVM does not wait for daemon threads before shutting down. If the thread is already
+  /// attached, it stays a non-daemon thread.
+  pub fn attach_as_daemon(&self) -> crate::Result<Env<'static>> {
+    if let Some(env) = current() {
+      return Ok(env);
+    }
+
+    let jni_env = self.vm.attach_current_thread_as_daemon()?;
+    set_current(&self.vm, jni_env)
+  }
+
+  /// Gets the JNI environment of the current thread, which must be attached to the JVM
+  ///
+  /// The thread that created the JVM is attached. Wrap the result with `Env::new` to use jnat.
+  pub fn get_jni_env(&self) -> crate::Result<JNIEnv<'_>> {
+    Ok(self.vm.get_env()?)
+  }
+
+  /// Gets the wrapped JavaVM
+  pub fn get_java_vm(&self) -> &JavaVM {
+    &self.vm
+  }
+}
+
+#[cfg(feature = "invocation")]
+impl Drop for JavaVm {
+  fn drop(&mut self) {
+    if let Some(library) = self.library.take() {
+      // Nothing can be done about a failure while dropping
+      let _ = unsafe { self.vm.destroy() };
+
+      // Threads of the JVM can outlive DestroyJavaVM, and a JVM cannot be created again in the
+      // process, so libjvm stays loaded
+      std::mem::forget(library);
+    }
+  }
+}
+
+/// Gets the environment stored for the current thread, if the thread is still attached with it
+fn current() -> Option<Env<'static>> {
+  CURRENT.with(|current| {
+    let mut current = current.borrow_mut();
+    let stored = current.as_ref()?;
+
+    // An AttachGuard created before the environment was stored detaches the thread when dropped
+    let attached = match stored.vm.get_env() {
+      Ok(jni_env) => jni_env.get_raw() == stored.jni_env.get_raw(),
+      Err(_) => false,
+    };
+
+    if !attached {
+      // Environments returned before may still borrow the box, so it is never freed
+      Box::leak(current.take()?.jni_env);
+      return None;
+    }
+
+    let jni_env: *const JNIEnv<'static> = &*stored.jni_env;
+    // The box is never freed while the thread runs
+    Some(Env::new(unsafe { &*jni_env }))
+  })
+}
+
+/// Stores the environment of the current thread
+///
+/// # Arguments
+///
+/// * `vm` - The JVM the thread is attached to
+/// * `jni_env` - The environment
+fn set_current(vm: &JavaVM, jni_env: JNIEnv) -> crate::Result<Env<'static>> {
+  let vm = unsafe { JavaVM::from_raw(vm.get_java_vm_pointer()) }?;
+  let jni_env = unsafe { JNIEnv::from_raw(jni_env.get_raw()) }?;
+
+  CURRENT.with(|current| {
+    *current.borrow_mut() = Some(Current {
+      jni_env: Box::new(jni_env),
+      vm,
+    })
+  });
+  Ok(current().expect("the environment was just stored"))
+}
+
+/// Gets the environment of the current thread, attaching it to the stored JVM if needed
+pub(crate) fn current_env() -> crate::Result<Env<'static>> {
+  if let Some(env) = current() {
+    return Ok(env);
+  }
+
+  let vm = JavaVm::global().ok_or(Error::NoJavaVm)?;
+  let jni_env = vm.vm.attach_current_thread_permanently()?;
+
+  set_current(&vm.vm, jni_env)
+}
+
+/// Stores the JVM that loaded the library, and negotiates the JNI version of the library
+///
+/// The newest version jnat knows is tried first, falling back to older versions down to the one
+/// the library needs. Returns the environment of the thread loading the library with the version
+/// `JNI_OnLoad` should return, or the code it should return if the JVM does not support the
+/// version the library needs. This is used by the functions generated with `#[on_load]`.
+///
+/// # Safety
+///
+/// `vm` must be the JVM passed to `JNI_OnLoad`
+///
+/// # Arguments
+///
+/// * `vm` - The JVM
+/// * `version` - The oldest JNI version the library supports
+pub unsafe fn load_java_vm(
+  vm: *mut sys::JavaVM,
+  version: Version,
+) -> Result<(JNIEnv<'static>, Version), jint> {
+  let java_vm = unsafe { JavaVM::from_raw(vm) }.map_err(|_| JNI_ERR)?;
+  JavaVm::new(java_vm).store();
+
+  let get_env = unsafe { (**vm).GetEnv }.ok_or(JNI_ERR)?;
+
+  for candidate in Version::KNOWN.into_iter().filter(|known| *known >= version) {
+    let mut jni_env = ptr::null_mut();
+
+    match unsafe { get_env(vm, &mut jni_env, candidate.to_raw()) } {
+      JNI_OK => {
+        let jni_env = unsafe { JNIEnv::from_raw(jni_env as *mut sys::JNIEnv) };
+        return jni_env
+          .map(|jni_env| (jni_env, candidate))
+          .map_err(|_| JNI_ERR);
+      }
+      JNI_EVERSION => continue,
+      code => return Err(code),
+    }
+  }
+
+  Err(JNI_EVERSION)
+}
+
+/// Gets the environment of the thread unloading the library, if it is attached
+///
+/// This is used by the functions generated with `#[on_unload]`.
+///
+/// # Safety
+///
+/// `vm` must be the JVM passed to `JNI_OnUnload`
+///
+/// # Arguments
+///
+/// * `vm` - The JVM
+pub unsafe fn unload_java_vm(vm: *mut sys::JavaVM) -> Option<JNIEnv<'static>> {
+  let java_vm = unsafe { JavaVM::from_raw(vm) }.ok()?;
+  let jni_env = java_vm.get_env().ok()?;
+
+  unsafe { JNIEnv::from_raw(jni_env.get_raw()) }.ok()
+}